@@ -0,0 +1,648 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{Map, Value};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+use crate::models::{
+    ApiError, DecodeAccountRequest, DecodeEventsRequest, DecodeEventsResponse, DecodedAccountResponse,
+    DecodedEvent, IdlInstructionRequest, IdlInstructionResponse, IdlListResponse, IdlSummaryResponse,
+    ResolvedAccount,
+};
+use crate::utils::{
+    ApiJson, Idl, IdlAccount, IdlAccountItem, IdlInstruction, IdlSeed, arg_seed_bytes, const_seed_bytes,
+    encode_idl_value, find_named, names_match, parse_idl, validate_pubkey, instruction_to_transaction,
+};
+
+const MAX_IDL_NAME_LENGTH: usize = 64;
+
+/// Anchor IDLs uploaded under a name. Kept in memory, and also written to
+/// `dir` (the `IDL_DIR` environment variable) when one is configured so
+/// they survive restarts.
+pub struct IdlRegistry {
+    idls: RwLock<HashMap<String, Arc<Idl>>>,
+    dir: Option<PathBuf>,
+}
+
+impl IdlRegistry {
+    /// Loads every `<name>.json` in `dir`. Files that don't parse are
+    /// reported and skipped rather than stopping the server.
+    pub fn load(dir: Option<PathBuf>) -> Self {
+        let mut idls = HashMap::new();
+
+        if let Some(entries) = dir.as_ref().and_then(|dir| std::fs::read_dir(dir).ok()) {
+            for path in entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()) {
+                let Some(name) = path.file_stem().and_then(|stem| stem.to_str()).filter(|_| {
+                    path.extension().is_some_and(|extension| extension == "json")
+                }) else {
+                    continue;
+                };
+
+                let parsed = std::fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|contents| serde_json::from_str(&contents).map_err(|e| e.to_string()))
+                    .and_then(|value| parse_idl(value).map_err(|e| e.to_string()));
+                match parsed {
+                    Ok(idl) => {
+                        idls.insert(name.to_string(), Arc::new(idl));
+                    }
+                    Err(e) => eprintln!("Skipping IDL {}: {}", path.display(), e),
+                }
+            }
+        }
+
+        IdlRegistry { idls: RwLock::new(idls), dir }
+    }
+
+    pub fn get(&self, name: &str) -> Result<Arc<Idl>, ApiError> {
+        self.idls
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(name)
+            .cloned()
+            .ok_or_else(|| ApiError::NotFound(format!("IDL '{}'", name)))
+    }
+
+    fn insert(&self, name: String, idl: Arc<Idl>) {
+        self.idls
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(name, idl);
+    }
+
+    fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.idls
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+/// Names double as file names, so keep them to a safe character set.
+fn validate_idl_name(name: &str) -> Result<(), ApiError> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_IDL_NAME_LENGTH
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid {
+        return Err(ApiError::invalid_field(
+            "name",
+            format!("must be 1-{} characters of letters, digits, '_' or '-'", MAX_IDL_NAME_LENGTH),
+        ));
+    }
+    Ok(())
+}
+
+/// Programs older IDLs list by name only; 0.30+ IDLs carry their address.
+fn well_known_address(name: &str) -> Option<Pubkey> {
+    [
+        ("systemProgram", system_program::id()),
+        ("tokenProgram", spl_token::id()),
+        ("associatedTokenProgram", spl_associated_token_account::id()),
+        ("rent", sysvar::rent::id()),
+        ("clock", sysvar::clock::id()),
+    ]
+    .into_iter()
+    .find(|(known, _)| names_match(known, name))
+    .map(|(_, address)| address)
+}
+
+struct FlatAccount<'a> {
+    name: String,
+    account: &'a IdlAccount,
+    provided: Option<&'a Value>,
+}
+
+/// Flattens nested account groups in order. Their accounts are named
+/// `group.account` and passed as nested objects.
+fn flatten_accounts<'a>(
+    items: &'a [IdlAccountItem],
+    prefix: &str,
+    provided: Option<&'a Map<String, Value>>,
+    out: &mut Vec<FlatAccount<'a>>,
+) {
+    for item in items {
+        match item {
+            IdlAccountItem::Single(account) => out.push(FlatAccount {
+                name: format!("{}{}", prefix, account.name),
+                account,
+                provided: provided.and_then(|provided| find_named(provided, &account.name)),
+            }),
+            IdlAccountItem::Composite(group) => {
+                let nested = provided
+                    .and_then(|provided| find_named(provided, &group.name))
+                    .and_then(Value::as_object);
+                flatten_accounts(&group.accounts, &format!("{}{}.", prefix, group.name), nested, out);
+            }
+        }
+    }
+}
+
+/// Looks up another account of the instruction by the name a seed uses.
+fn find_account(accounts: &[FlatAccount], path: &str) -> Option<usize> {
+    accounts.iter().position(|account| names_match(&account.name, path))
+        .or_else(|| accounts.iter().position(|account| names_match(&account.account.name, path)))
+}
+
+/// Derives a PDA, or returns `None` while an account it depends on is
+/// still unresolved.
+fn derive_pda(
+    idl: &Idl,
+    instruction: &IdlInstruction,
+    payload: &IdlInstructionRequest,
+    program_id: &Pubkey,
+    accounts: &[FlatAccount],
+    resolved: &[Option<Pubkey>],
+    index: usize,
+) -> Result<Option<Pubkey>, ApiError> {
+    let Some(pda) = &accounts[index].account.pda else {
+        return Ok(None);
+    };
+    let field = format!("accounts.{}", accounts[index].name);
+
+    let seed_bytes = |seed: &IdlSeed| -> Result<Option<Vec<u8>>, ApiError> {
+        match seed {
+            IdlSeed::Const { ty, value } => const_seed_bytes(idl, &field, ty.as_ref(), value).map(Some),
+            IdlSeed::Arg { path } => arg_seed_bytes(idl, &instruction.args, &payload.args, path).map(Some),
+            IdlSeed::Account { path } => match find_account(accounts, path) {
+                Some(other) => Ok(resolved[other].map(|key| key.to_bytes().to_vec())),
+                // A path into another account's data can only be read on chain
+                None => Err(ApiError::invalid_field(
+                    &field,
+                    format!("PDA seed '{}' depends on account data; pass the address directly", path),
+                )),
+            },
+        }
+    };
+
+    let mut seeds = Vec::with_capacity(pda.seeds.len());
+    for seed in &pda.seeds {
+        let Some(bytes) = seed_bytes(seed)? else {
+            return Ok(None);
+        };
+        seeds.push(bytes);
+    }
+
+    let owner = match &pda.program {
+        Some(seed) => {
+            let Some(bytes) = seed_bytes(seed)? else {
+                return Ok(None);
+            };
+            Pubkey::try_from(bytes.as_slice())
+                .map_err(|_| ApiError::invalid_field(&field, "PDA program seed is not a 32-byte address"))?
+        }
+        None => *program_id,
+    };
+
+    let seed_refs: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    Pubkey::try_find_program_address(&seed_refs, &owner)
+        .map(|(address, _)| Some(address))
+        .ok_or_else(|| ApiError::invalid_field(&field, "PDA seeds are too long or too many"))
+}
+
+/// Builds an instruction from a registered IDL: discriminator plus
+/// Borsh-encoded args, and accounts in IDL order with PDAs derived.
+pub fn build_idl_instruction(
+    idl: &Idl,
+    instruction_name: &str,
+    payload: &IdlInstructionRequest,
+) -> Result<(Instruction, Vec<ResolvedAccount>), ApiError> {
+    let instruction = idl.instruction(instruction_name)
+        .ok_or_else(|| ApiError::NotFound(format!("Instruction '{}'", instruction_name)))?;
+
+    let program_id = match &payload.program_id {
+        Some(program_id) => validate_pubkey(program_id)
+            .map_err(|e| ApiError::invalid_pubkey("programId", e))?,
+        None => idl.program_id
+            .ok_or_else(|| ApiError::invalid_field("programId", "the IDL has no program address; pass programId"))?,
+    };
+
+    let mut data = instruction.discriminator.clone();
+    for arg in &instruction.args {
+        let field = format!("args.{}", arg.name);
+        let value = find_named(&payload.args, &arg.name)
+            .ok_or_else(|| ApiError::invalid_field(&field, "missing"))?;
+        encode_idl_value(idl, &field, &arg.ty, value, &mut data)?;
+    }
+
+    let mut accounts = Vec::new();
+    flatten_accounts(&instruction.accounts, "", Some(&payload.accounts), &mut accounts);
+
+    let mut resolved: Vec<Option<Pubkey>> = Vec::with_capacity(accounts.len());
+    for account in &accounts {
+        let field = format!("accounts.{}", account.name);
+        let key = match (account.provided, &account.account.address) {
+            (Some(provided), _) => {
+                let provided = provided.as_str()
+                    .ok_or_else(|| ApiError::invalid_field(&field, "expected a base58 pubkey"))?;
+                Some(validate_pubkey(provided).map_err(|e| ApiError::invalid_pubkey(&field, e))?)
+            }
+            (None, Some(address)) => Some(Pubkey::from_str(address).map_err(|e| ApiError::invalid_pubkey(&field, e))?),
+            (None, None) if account.account.pda.is_none() => well_known_address(&account.account.name),
+            (None, None) => None,
+        };
+        resolved.push(key);
+    }
+
+    // PDAs can be seeded by other PDAs, so keep deriving until nothing changes
+    loop {
+        let mut progressed = false;
+        for index in 0..accounts.len() {
+            if resolved[index].is_none() {
+                if let Some(key) = derive_pda(idl, instruction, payload, &program_id, &accounts, &resolved, index)? {
+                    resolved[index] = Some(key);
+                    progressed = true;
+                }
+            }
+        }
+        if !progressed {
+            break;
+        }
+    }
+
+    let mut metas = Vec::with_capacity(accounts.len() + payload.remaining_accounts.len());
+    let mut named = Vec::with_capacity(metas.capacity());
+    for (account, key) in accounts.iter().zip(resolved) {
+        let meta = match key {
+            Some(key) => AccountMeta {
+                pubkey: key,
+                is_signer: account.account.signer,
+                is_writable: account.account.writable,
+            },
+            // Anchor reads the program id in an optional account's slot as None
+            None if account.account.optional => AccountMeta::new_readonly(program_id, false),
+            None if account.account.pda.is_some() => return Err(ApiError::invalid_field(
+                format!("accounts.{}", account.name),
+                "missing, and its PDA seeds reference an account that is missing too",
+            )),
+            None => return Err(ApiError::invalid_field(format!("accounts.{}", account.name), "missing")),
+        };
+        named.push(ResolvedAccount {
+            name: account.name.clone(),
+            pubkey: meta.pubkey.to_string(),
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        });
+        metas.push(meta);
+    }
+
+    for (index, account) in payload.remaining_accounts.iter().enumerate() {
+        let pubkey = validate_pubkey(&account.pubkey)
+            .map_err(|e| ApiError::invalid_pubkey(format!("remainingAccounts[{}].pubkey", index), e))?;
+        named.push(ResolvedAccount {
+            name: format!("remainingAccounts[{}]", index),
+            pubkey: pubkey.to_string(),
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        });
+        metas.push(AccountMeta { pubkey, is_signer: account.is_signer, is_writable: account.is_writable });
+    }
+
+    Ok((Instruction { program_id, accounts: metas, data }, named))
+}
+
+pub async fn register_idl(
+    State(registry): State<Arc<IdlRegistry>>,
+    Path(name): Path<String>,
+    ApiJson(raw): ApiJson<Value>,
+) -> Result<Json<IdlSummaryResponse>, ApiError> {
+    validate_idl_name(&name)?;
+    let idl = Arc::new(parse_idl(raw.clone())?);
+
+    let persisted = match &registry.dir {
+        Some(dir) => {
+            let contents = serde_json::to_vec_pretty(&raw).map_err(|e| ApiError::StorageFailed(e.to_string()))?;
+            tokio::fs::create_dir_all(dir).await.map_err(|e| ApiError::StorageFailed(e.to_string()))?;
+            tokio::fs::write(dir.join(format!("{}.json", name)), contents)
+                .await
+                .map_err(|e| ApiError::StorageFailed(e.to_string()))?;
+            true
+        }
+        None => false,
+    };
+
+    let response = IdlSummaryResponse {
+        name: name.clone(),
+        program_id: idl.program_id.map(|program_id| program_id.to_string()),
+        instructions: idl.instructions.iter().map(|instruction| instruction.name.clone()).collect(),
+        accounts: idl.accounts.iter().map(|account| account.name.clone()).collect(),
+        events: idl.events.iter().map(|event| event.name.clone()).collect(),
+        persisted,
+    };
+    registry.insert(name, idl);
+
+    Ok(Json(response))
+}
+
+pub async fn list_idls(
+    State(registry): State<Arc<IdlRegistry>>,
+) -> Json<IdlListResponse> {
+    Json(IdlListResponse { idls: registry.names() })
+}
+
+pub async fn idl_instruction(
+    State(registry): State<Arc<IdlRegistry>>,
+    Path((name, instruction_name)): Path<(String, String)>,
+    ApiJson(payload): ApiJson<IdlInstructionRequest>,
+) -> Result<Json<IdlInstructionResponse>, ApiError> {
+    let idl = registry.get(&name)?;
+    let (instruction, accounts) = build_idl_instruction(&idl, &instruction_name, &payload)?;
+
+    // Fees default to the first signing account. An explicit feePayer
+    // always wins, so the program id below never ends up paying.
+    let first_signer = instruction.accounts.iter().find(|account| account.is_signer).map(|account| account.pubkey);
+    if first_signer.is_none() && payload.options.fee_payer.is_none() {
+        return Err(ApiError::invalid_field("feePayer", "required when no account is a signer"));
+    }
+    let default_fee_payer = first_signer.unwrap_or(instruction.program_id);
+    let transaction = instruction_to_transaction(std::slice::from_ref(&instruction), default_fee_payer, &payload.options)?;
+
+    Ok(Json(IdlInstructionResponse {
+        transaction,
+        instruction_data: STANDARD.encode(&instruction.data),
+        accounts,
+    }))
+}
+
+pub async fn decode_idl_account(
+    State(registry): State<Arc<IdlRegistry>>,
+    Path(name): Path<String>,
+    ApiJson(payload): ApiJson<DecodeAccountRequest>,
+) -> Result<Json<DecodedAccountResponse>, ApiError> {
+    let idl = registry.get(&name)?;
+    let data = STANDARD.decode(&payload.data)
+        .map_err(|e| ApiError::invalid_field("data", format!("failed to decode base64: {}", e)))?;
+
+    let account = match &payload.account {
+        Some(account_name) => {
+            let account = idl.accounts.iter()
+                .find(|account| names_match(&account.name, account_name))
+                .ok_or_else(|| ApiError::NotFound(format!("Account '{}'", account_name)))?;
+            if !data.starts_with(&account.discriminator) {
+                return Err(ApiError::invalid_field("data", format!("discriminator does not match {}", account.name)));
+            }
+            account
+        }
+        None => idl.account_for(&data)
+            .ok_or_else(|| ApiError::invalid_field("data", "discriminator matches no account in the IDL"))?,
+    };
+
+    // Accounts are often allocated larger than their data, so trailing
+    // bytes are left alone
+    let decoded = idl.decode_discriminated("data", account, &data)?;
+
+    Ok(Json(DecodedAccountResponse {
+        account: account.name.clone(),
+        data: decoded,
+    }))
+}
+
+/// Anchor's `emit!` writes events as `Program data: <base64>` lines. Only
+/// lines logged while this program is the one executing are decoded, so
+/// events from other programs in the same transaction are skipped.
+pub async fn decode_idl_events(
+    State(registry): State<Arc<IdlRegistry>>,
+    Path(name): Path<String>,
+    ApiJson(payload): ApiJson<DecodeEventsRequest>,
+) -> Result<Json<DecodeEventsResponse>, ApiError> {
+    let idl = registry.get(&name)?;
+    let program_id = idl.program_id.map(|program_id| program_id.to_string());
+
+    let mut invocations: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    for (index, log) in payload.logs.iter().enumerate() {
+        if let Some(data) = log.strip_prefix("Program data: ") {
+            let current = invocations.last().copied();
+            if program_id.is_some() && current.is_some() && current != program_id.as_deref() {
+                continue;
+            }
+
+            let field = format!("logs[{}]", index);
+            let Ok(data) = STANDARD.decode(data.trim()) else {
+                continue;
+            };
+            let Some(event) = idl.event_for(&data) else {
+                continue;
+            };
+            // One malformed event shouldn't hide the others in the transaction
+            let (data, error) = match idl.decode_discriminated(&field, event, &data) {
+                Ok(data) => (Some(data), None),
+                Err(error) => (None, Some(error.to_string())),
+            };
+            events.push(DecodedEvent {
+                name: event.name.clone(),
+                data,
+                error,
+                log_index: index,
+            });
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            match (words.next(), words.next()) {
+                (Some(program), Some("invoke")) => invocations.push(program),
+                (Some(_), Some("success" | "failed:")) => {
+                    invocations.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(Json(DecodeEventsResponse { events }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use serde_json::json;
+
+    const NODE_DISCRIMINATOR: [u8; 8] = [9; 8];
+
+    fn registry() -> State<Arc<IdlRegistry>> {
+        State(Arc::new(IdlRegistry::load(None)))
+    }
+
+    /// A linked list node, `Node { next: Option<Box<Node>> }`, as both an
+    /// account and an event.
+    fn node_idl() -> Value {
+        json!({
+            "address": "11111111111111111111111111111111",
+            "instructions": [],
+            "accounts": [{ "name": "Node", "discriminator": NODE_DISCRIMINATOR }],
+            "events": [{ "name": "Node", "discriminator": NODE_DISCRIMINATOR }],
+            "types": [{
+                "name": "Node",
+                "type": { "kind": "struct", "fields": [{ "name": "next", "type": { "option": { "defined": { "name": "Node" } } } }] }
+            }]
+        })
+    }
+
+    fn nested_nodes(levels: usize) -> Vec<u8> {
+        let mut data = NODE_DISCRIMINATOR.to_vec();
+        data.extend(std::iter::repeat_n(1, levels));
+        data.push(0);
+        data
+    }
+
+    async fn register(registry: &State<Arc<IdlRegistry>>, name: &str, idl: Value) -> Result<(), ApiError> {
+        register_idl(State(registry.0.clone()), Path(name.to_string()), ApiJson(idl)).await.map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn rejects_self_containing_types() {
+        let registry = registry();
+        let alias = json!({
+            "instructions": [],
+            "accounts": [{ "name": "A", "discriminator": [9, 9, 9, 9, 9, 9, 9, 9] }],
+            "types": [{ "name": "A", "type": { "kind": "type", "alias": { "defined": { "name": "A" } } } }]
+        });
+        let Err(error) = register(&registry, "loop", alias).await else {
+            panic!("cyclic alias accepted");
+        };
+        assert_eq!(error.field(), Some("types"));
+
+        let structs = json!({
+            "instructions": [],
+            "types": [
+                { "name": "A", "type": { "kind": "struct", "fields": [{ "name": "b", "type": { "defined": { "name": "B" } } }] } },
+                { "name": "B", "type": { "kind": "struct", "fields": [{ "name": "a", "type": { "array": [{ "defined": { "name": "A" } }, 2] } }] } }
+            ]
+        });
+        assert!(register(&registry, "loop", structs).await.is_err());
+        assert!(registry.get("loop").is_err());
+    }
+
+    /// `A0` aliases `A1`, which aliases `A2`, and so on down to a `u8`.
+    fn alias_chain(length: usize) -> Value {
+        let mut types: Vec<Value> = (0..length)
+            .map(|i| json!({ "name": format!("A{}", i), "type": { "kind": "type", "alias": { "defined": { "name": format!("A{}", i + 1) } } } }))
+            .collect();
+        types.push(json!({ "name": format!("A{}", length), "type": { "kind": "type", "alias": "u8" } }));
+        json!({ "instructions": [], "types": types })
+    }
+
+    #[tokio::test]
+    async fn rejects_long_type_chains() {
+        let registry = registry();
+        register(&registry, "short", alias_chain(10)).await.unwrap();
+
+        let Err(error) = register(&registry, "long", alias_chain(20_000)).await else {
+            panic!("long alias chain accepted");
+        };
+        assert_eq!(error.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error.field(), Some("types"));
+        assert!(registry.get("long").is_err());
+    }
+
+    #[tokio::test]
+    async fn decodes_recursive_types_within_the_depth_limit() {
+        let registry = registry();
+        register(&registry, "node", node_idl()).await.unwrap();
+
+        let payload = DecodeAccountRequest { data: STANDARD.encode(nested_nodes(3)), account: None };
+        let Ok(Json(decoded)) = decode_idl_account(State(registry.0.clone()), Path("node".to_string()), ApiJson(payload)).await else {
+            panic!("shallow nesting fails to decode");
+        };
+        assert_eq!(decoded.data, json!({ "next": { "next": { "next": { "next": null } } } }));
+    }
+
+    #[tokio::test]
+    async fn rejects_deeply_nested_account_data() {
+        let registry = registry();
+        register(&registry, "node", node_idl()).await.unwrap();
+
+        let payload = DecodeAccountRequest { data: STANDARD.encode(nested_nodes(100_000)), account: None };
+        let Err(error) = decode_idl_account(State(registry.0.clone()), Path("node".to_string()), ApiJson(payload)).await else {
+            panic!("deep nesting decoded");
+        };
+        assert_eq!(error.code(), "INVALID_FIELD");
+    }
+
+    #[tokio::test]
+    async fn reports_deeply_nested_event_data_per_event() {
+        let registry = registry();
+        register(&registry, "node", node_idl()).await.unwrap();
+
+        let payload = DecodeEventsRequest {
+            logs: vec![
+                format!("Program data: {}", STANDARD.encode(nested_nodes(100_000))),
+                format!("Program data: {}", STANDARD.encode(nested_nodes(1))),
+            ],
+        };
+        let Ok(Json(decoded)) = decode_idl_events(State(registry.0.clone()), Path("node".to_string()), ApiJson(payload)).await else {
+            panic!("one bad event failed the whole call");
+        };
+        let [deep, shallow] = decoded.events.as_slice() else {
+            panic!("expected two events");
+        };
+        assert!(deep.data.is_none());
+        assert!(deep.error.as_deref().is_some_and(|error| error.contains("logs[0]")));
+        assert_eq!(shallow.data, Some(json!({ "next": { "next": null } })));
+        assert_eq!(shallow.log_index, 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_array_lengths_past_the_data() {
+        let registry = registry();
+        let idl = json!({
+            "instructions": [],
+            "accounts": [{ "name": "Big", "discriminator": [8, 8, 8, 8, 8, 8, 8, 8] }],
+            "types": [
+                { "name": "Big", "type": { "kind": "struct", "fields": [{ "name": "items", "type": { "array": [{ "defined": { "name": "Empty" } }, 1_000_000_000_000u64] } }] } },
+                { "name": "Empty", "type": { "kind": "struct", "fields": [] } }
+            ]
+        });
+        register(&registry, "big", idl).await.unwrap();
+
+        let payload = DecodeAccountRequest { data: STANDARD.encode([8u8; 8]), account: None };
+        let Err(error) = decode_idl_account(State(registry.0.clone()), Path("big".to_string()), ApiJson(payload)).await else {
+            panic!("oversized array decoded");
+        };
+        assert_eq!(error.field(), Some("data.items"));
+    }
+
+    #[tokio::test]
+    async fn rejects_too_many_zero_sized_values() {
+        let registry = registry();
+        let idl = json!({
+            "instructions": [],
+            "accounts": [
+                { "name": "Grid", "discriminator": [6, 6, 6, 6, 6, 6, 6, 6] },
+                { "name": "Rows", "discriminator": [7, 7, 7, 7, 7, 7, 7, 7] }
+            ],
+            "types": [
+                { "name": "Grid", "type": { "kind": "struct", "fields": [{ "name": "cells", "type": { "array": [{ "array": [{ "defined": { "name": "Empty" } }, 5000] }, 5000] } }] } },
+                { "name": "Rows", "type": { "kind": "struct", "fields": [{ "name": "rows", "type": { "vec": { "array": [{ "defined": { "name": "Empty" } }, 5000] } } }] } },
+                { "name": "Empty", "type": { "kind": "struct", "fields": [] } }
+            ]
+        });
+        register(&registry, "empty", idl).await.unwrap();
+
+        let mut grid = vec![6u8; 8];
+        grid.resize(8 + 5000, 0);
+        let mut rows = vec![7u8; 8];
+        rows.extend(5000u32.to_le_bytes());
+        rows.resize(8 + 4 + 5000, 0);
+
+        for (data, field) in [(grid, "data.cells"), (rows, "data.rows")] {
+            let payload = DecodeAccountRequest { data: STANDARD.encode(data), account: None };
+            let Err(error) = decode_idl_account(State(registry.0.clone()), Path("empty".to_string()), ApiJson(payload)).await else {
+                panic!("{} decoded", field);
+            };
+            assert_eq!(error.status(), StatusCode::BAD_REQUEST);
+            assert!(error.field().is_some_and(|path| path.starts_with(field)));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use axum::response::Json;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_program::instruction::{AccountMeta, Instruction};
+use crate::models::{
+    ApiError, CustomInstructionRequest, CustomInstructionResponse, InstructionDataSpec,
+};
+use crate::utils::{
+    ApiJson, BorshType, encode_borsh, validate_pubkey, instruction_to_response, instruction_to_transaction,
+};
+
+fn instruction_data(data: Option<&InstructionDataSpec>) -> Result<Vec<u8>, ApiError> {
+    let Some(data) = data else {
+        return Ok(Vec::new());
+    };
+
+    match data {
+        InstructionDataSpec::Hex { value } => hex::decode(value.trim_start_matches("0x"))
+            .map_err(|e| ApiError::invalid_field("data.value", format!("failed to decode hex: {}", e))),
+        InstructionDataSpec::Base58 { value } => bs58::decode(value).into_vec()
+            .map_err(|e| ApiError::invalid_field("data.value", format!("failed to decode base58: {}", e))),
+        InstructionDataSpec::Base64 { value } => STANDARD.decode(value)
+            .map_err(|e| ApiError::invalid_field("data.value", format!("failed to decode base64: {}", e))),
+        InstructionDataSpec::Borsh { fields } => {
+            let mut bytes = Vec::new();
+            for (index, field) in fields.iter().enumerate() {
+                let ty: BorshType = field.field_type.parse()
+                    .map_err(|e| ApiError::invalid_field(format!("data.fields[{}].type", index), e))?;
+                encode_borsh(&format!("data.fields[{}].value", index), &ty, &field.value, &mut bytes)?;
+            }
+            Ok(bytes)
+        }
+    }
+}
+
+pub fn build_custom_instruction(payload: &CustomInstructionRequest) -> Result<Instruction, ApiError> {
+    let program_id = validate_pubkey(&payload.program_id)
+        .map_err(|e| ApiError::invalid_pubkey("programId", e))?;
+
+    let accounts = payload
+        .accounts
+        .iter()
+        .enumerate()
+        .map(|(index, account)| {
+            let pubkey = validate_pubkey(&account.pubkey)
+                .map_err(|e| ApiError::invalid_pubkey(format!("accounts[{}].pubkey", index), e))?;
+            Ok(AccountMeta {
+                pubkey,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: instruction_data(payload.data.as_ref())?,
+    })
+}
+
+pub async fn custom_instruction(
+    ApiJson(payload): ApiJson<CustomInstructionRequest>,
+) -> Result<Json<CustomInstructionResponse>, ApiError> {
+    let instruction = build_custom_instruction(&payload)?;
+
+    let transaction = if payload.include_transaction {
+        // Fees default to the first signing account. An explicit feePayer
+        // always wins, so the program id below never ends up paying.
+        let first_signer = instruction.accounts.iter().find(|account| account.is_signer).map(|account| account.pubkey);
+        if first_signer.is_none() && payload.options.fee_payer.is_none() {
+            return Err(ApiError::invalid_field("feePayer", "required when no account is a signer"));
+        }
+        let default_fee_payer = first_signer.unwrap_or(instruction.program_id);
+        Some(instruction_to_transaction(std::slice::from_ref(&instruction), default_fee_payer, &payload.options)?)
+    } else {
+        if !payload.options.is_empty() {
+            return Err(ApiError::invalid_field(
+                "includeTransaction",
+                "transaction options are only used with \"includeTransaction\": true",
+            ));
+        }
+        None
+    };
+
+    Ok(Json(CustomInstructionResponse {
+        instruction: instruction_to_response(instruction),
+        transaction,
+    }))
+}
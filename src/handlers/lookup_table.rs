@@ -0,0 +1,145 @@
+use axum::response::Json;
+use solana_program::address_lookup_table::instruction;
+use solana_sdk::pubkey::Pubkey;
+use crate::models::{
+    ApiError, TransactionResponse, LookupTableResponse, CreateLookupTableRequest,
+    ExtendLookupTableRequest, LookupTableAuthorityRequest, CloseLookupTableRequest,
+};
+use crate::utils::{ApiJson, InstructionBatch, validate_pubkey, instruction_to_transaction};
+
+fn optional_payer(payer: &Option<String>, authority: Pubkey) -> Result<Pubkey, ApiError> {
+    match payer {
+        Some(payer) => validate_pubkey(payer).map_err(|e| ApiError::invalid_pubkey("payer", e)),
+        None => Ok(authority),
+    }
+}
+
+pub fn build_create_lookup_table(
+    payload: &CreateLookupTableRequest,
+) -> Result<(InstructionBatch, Pubkey), ApiError> {
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+    let payer = optional_payer(&payload.payer, authority)?;
+
+    let (instruction, lookup_table) = instruction::create_lookup_table(authority, payer, payload.recent_slot);
+
+    let batch = InstructionBatch {
+        instructions: vec![instruction],
+        default_fee_payer: payer,
+    };
+    Ok((batch, lookup_table))
+}
+
+pub fn build_extend_lookup_table(payload: &ExtendLookupTableRequest) -> Result<InstructionBatch, ApiError> {
+    let lookup_table = validate_pubkey(&payload.lookup_table)
+        .map_err(|e| ApiError::invalid_pubkey("lookupTable", e))?;
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+    let payer = optional_payer(&payload.payer, authority)?;
+
+    if payload.addresses.is_empty() {
+        return Err(ApiError::invalid_field("addresses", "at least one address is required"));
+    }
+
+    let new_addresses = payload
+        .addresses
+        .iter()
+        .enumerate()
+        .map(|(i, address)| {
+            validate_pubkey(address).map_err(|e| ApiError::invalid_pubkey(format!("addresses[{}]", i), e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let instruction = instruction::extend_lookup_table(lookup_table, authority, Some(payer), new_addresses);
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction],
+        default_fee_payer: payer,
+    })
+}
+
+pub fn build_freeze_lookup_table(payload: &LookupTableAuthorityRequest) -> Result<InstructionBatch, ApiError> {
+    let lookup_table = validate_pubkey(&payload.lookup_table)
+        .map_err(|e| ApiError::invalid_pubkey("lookupTable", e))?;
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction::freeze_lookup_table(lookup_table, authority)],
+        default_fee_payer: authority,
+    })
+}
+
+pub fn build_deactivate_lookup_table(payload: &LookupTableAuthorityRequest) -> Result<InstructionBatch, ApiError> {
+    let lookup_table = validate_pubkey(&payload.lookup_table)
+        .map_err(|e| ApiError::invalid_pubkey("lookupTable", e))?;
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction::deactivate_lookup_table(lookup_table, authority)],
+        default_fee_payer: authority,
+    })
+}
+
+pub fn build_close_lookup_table(payload: &CloseLookupTableRequest) -> Result<InstructionBatch, ApiError> {
+    let lookup_table = validate_pubkey(&payload.lookup_table)
+        .map_err(|e| ApiError::invalid_pubkey("lookupTable", e))?;
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+    let recipient = validate_pubkey(&payload.recipient)
+        .map_err(|e| ApiError::invalid_pubkey("recipient", e))?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction::close_lookup_table(lookup_table, authority, recipient)],
+        default_fee_payer: authority,
+    })
+}
+
+pub async fn create_lookup_table(
+    ApiJson(payload): ApiJson<CreateLookupTableRequest>,
+) -> Result<Json<LookupTableResponse>, ApiError> {
+    let (batch, lookup_table) = build_create_lookup_table(&payload)?;
+    let transaction = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(LookupTableResponse {
+        transaction,
+        lookup_table_address: lookup_table.to_string(),
+    }))
+}
+
+pub async fn extend_lookup_table(
+    ApiJson(payload): ApiJson<ExtendLookupTableRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_extend_lookup_table(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn freeze_lookup_table(
+    ApiJson(payload): ApiJson<LookupTableAuthorityRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_freeze_lookup_table(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn deactivate_lookup_table(
+    ApiJson(payload): ApiJson<LookupTableAuthorityRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_deactivate_lookup_table(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn close_lookup_table(
+    ApiJson(payload): ApiJson<CloseLookupTableRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_close_lookup_table(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
@@ -1,38 +1,27 @@
-use axum::{extract::Json as ExtractJson, response::Json, http::StatusCode};
+use axum::response::Json;
 use ed25519_dalek::{SecretKey, PublicKey};
 use crate::models::{
-    SignatureResponse, VerificationResponse,
+    ApiError, SignatureResponse, VerificationResponse,
     SignMessageRequest, VerifyMessageRequest
 };
-use crate::utils::{decode_base58_secret, sign_message as crypto_sign_message, verify_signature};
+use crate::utils::{
+    ApiJson, decode_base58_secret, decode_base64_signature, validate_pubkey,
+    sign_message as crypto_sign_message, verify_signature,
+};
 
 pub async fn sign_message(
-    ExtractJson(payload): ExtractJson<SignMessageRequest>,
-) -> Result<Json<SignatureResponse>, StatusCode> {
-    let secret_bytes = match decode_base58_secret(&payload.secret) {
-        Ok(bytes) => bytes,
-        Err(e) => {
-            eprintln!("Invalid secret key: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    };
+    ApiJson(payload): ApiJson<SignMessageRequest>,
+) -> Result<Json<SignatureResponse>, ApiError> {
+    let secret_bytes = decode_base58_secret("secret", &payload.secret)?;
 
-    let signature = match crypto_sign_message(&payload.message, &secret_bytes) {
-        Ok(sig) => sig,
-        Err(e) => {
-            eprintln!("Failed to sign message: {}", e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let signature = crypto_sign_message(&payload.message, &secret_bytes)
+        .map_err(|e| ApiError::SigningFailed(e.to_string()))?;
 
     // Get the public key from the secret key
-    let secret_key = match SecretKey::from_bytes(&secret_bytes) {
-        Ok(sk) => sk,
-        Err(e) => {
-            eprintln!("Invalid secret key: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    };
+    let secret_key = SecretKey::from_bytes(&secret_bytes).map_err(|e| ApiError::InvalidSecret {
+        field: "secret".to_string(),
+        reason: e.to_string(),
+    })?;
     let public_key = PublicKey::from(&secret_key);
     let pubkey = bs58::encode(public_key.to_bytes()).into_string();
 
@@ -45,15 +34,14 @@ pub async fn sign_message(
 }
 
 pub async fn verify_message(
-    ExtractJson(payload): ExtractJson<VerifyMessageRequest>,
-) -> Result<Json<VerificationResponse>, StatusCode> {
-    let valid = match verify_signature(&payload.message, &payload.signature, &payload.pubkey) {
-        Ok(is_valid) => is_valid,
-        Err(e) => {
-            eprintln!("Failed to verify signature: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    };
+    ApiJson(payload): ApiJson<VerifyMessageRequest>,
+) -> Result<Json<VerificationResponse>, ApiError> {
+    let signature = decode_base64_signature("signature", &payload.signature)?;
+    let pubkey = validate_pubkey(&payload.pubkey)
+        .map_err(|e| ApiError::invalid_pubkey("pubkey", e))?;
+
+    let valid = verify_signature(payload.message.as_bytes(), &signature, &pubkey)
+        .map_err(|e| ApiError::invalid_pubkey("pubkey", e))?;
 
     let response_data = VerificationResponse { valid };
     Ok(Json(response_data))
@@ -0,0 +1,165 @@
+use mpl_token_metadata::{
+    accounts::{MasterEdition, Metadata},
+    instructions::{
+        CreateMasterEditionV3, CreateMasterEditionV3InstructionArgs, CreateMetadataAccountV3,
+        CreateMetadataAccountV3InstructionArgs, VerifyCollectionV1,
+    },
+    types::{Collection, Creator, DataV2},
+    MAX_CREATOR_LIMIT, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+};
+use solana_program::{instruction::Instruction, pubkey::Pubkey, system_program, sysvar};
+use crate::models::{ApiError, TokenMetadataSpec};
+use crate::utils::validate_pubkey;
+
+const MAX_SELLER_FEE_BASIS_POINTS: u16 = 10_000;
+
+/// Limits enforced by the Metaplex program; Token-2022 metadata is held to
+/// the same ones so a token can move between the two.
+pub(crate) fn validate_metadata_fields(field: &str, metadata: &TokenMetadataSpec) -> Result<(), ApiError> {
+    for (name, value, max) in [
+        ("name", &metadata.name, MAX_NAME_LENGTH),
+        ("symbol", &metadata.symbol, MAX_SYMBOL_LENGTH),
+        ("uri", &metadata.uri, MAX_URI_LENGTH),
+    ] {
+        if value.len() > max {
+            return Err(ApiError::invalid_field(
+                format!("{}.{}", field, name),
+                format!("must be at most {} bytes, got {}", max, value.len()),
+            ));
+        }
+    }
+
+    if metadata.seller_fee_basis_points > MAX_SELLER_FEE_BASIS_POINTS {
+        return Err(ApiError::invalid_field(
+            format!("{}.sellerFeeBasisPoints", field),
+            format!("must not exceed {}", MAX_SELLER_FEE_BASIS_POINTS),
+        ));
+    }
+
+    Ok(())
+}
+
+fn parse_creators(field: &str, metadata: &TokenMetadataSpec) -> Result<Option<Vec<Creator>>, ApiError> {
+    if metadata.creators.is_empty() {
+        return Ok(None);
+    }
+
+    if metadata.creators.len() > MAX_CREATOR_LIMIT {
+        return Err(ApiError::invalid_field(
+            format!("{}.creators", field),
+            format!("at most {} creators are allowed", MAX_CREATOR_LIMIT),
+        ));
+    }
+
+    let total_share: u32 = metadata.creators.iter().map(|creator| creator.share as u32).sum();
+    if total_share != 100 {
+        return Err(ApiError::invalid_field(
+            format!("{}.creators", field),
+            format!("shares must add up to 100, got {}", total_share),
+        ));
+    }
+
+    metadata
+        .creators
+        .iter()
+        .enumerate()
+        .map(|(index, creator)| {
+            let address = validate_pubkey(&creator.address)
+                .map_err(|e| ApiError::invalid_pubkey(format!("{}.creators[{}].address", field, index), e))?;
+
+            // Creators verify themselves later with their own signature
+            Ok(Creator { address, verified: false, share: creator.share })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+/// Metaplex `create_metadata_accounts_v3` for `mint`, along with the metadata
+/// PDA it creates. The mint must already be initialized when it runs.
+pub(crate) fn metaplex_metadata_instruction(
+    field: &str,
+    metadata: &TokenMetadataSpec,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    update_authority: &Pubkey,
+) -> Result<(Instruction, Pubkey), ApiError> {
+    validate_metadata_fields(field, metadata)?;
+    let creators = parse_creators(field, metadata)?;
+
+    let collection = metadata.collection.as_ref()
+        .map(|collection| validate_pubkey(collection)
+            .map_err(|e| ApiError::invalid_pubkey(format!("{}.collection", field), e)))
+        .transpose()?
+        .map(|key| Collection { verified: false, key });
+
+    let (metadata_address, _) = Metadata::find_pda(mint);
+    let instruction = CreateMetadataAccountV3 {
+        metadata: metadata_address,
+        mint: *mint,
+        mint_authority: *mint_authority,
+        payer: *payer,
+        update_authority: (*update_authority, false),
+        system_program: system_program::id(),
+        rent: None,
+    }
+    .instruction(CreateMetadataAccountV3InstructionArgs {
+        data: DataV2 {
+            name: metadata.name.clone(),
+            symbol: metadata.symbol.clone(),
+            uri: metadata.uri.clone(),
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            creators,
+            collection,
+            uses: None,
+        },
+        is_mutable: metadata.is_mutable.unwrap_or(true),
+        collection_details: None,
+    });
+
+    Ok((instruction, metadata_address))
+}
+
+/// Metaplex `create_master_edition_v3`, which makes `mint` an NFT: the mint
+/// and freeze authorities pass to the edition account, so the mint must
+/// already hold its single token. Returns the edition address too.
+pub(crate) fn master_edition_instruction(
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    update_authority: &Pubkey,
+    payer: &Pubkey,
+    max_supply: u64,
+) -> (Instruction, Pubkey) {
+    let (metadata_address, _) = Metadata::find_pda(mint);
+    let (edition_address, _) = MasterEdition::find_pda(mint);
+    let instruction = CreateMasterEditionV3 {
+        edition: edition_address,
+        mint: *mint,
+        update_authority: *update_authority,
+        mint_authority: *mint_authority,
+        payer: *payer,
+        metadata: metadata_address,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: None,
+    }
+    .instruction(CreateMasterEditionV3InstructionArgs { max_supply: Some(max_supply) });
+
+    (instruction, edition_address)
+}
+
+/// Metaplex `verify_collection_v1`, signed by the collection's update
+/// authority. Works for both sized and unsized collections.
+pub(crate) fn verify_collection_instruction(mint: &Pubkey, collection_mint: &Pubkey, authority: &Pubkey) -> Instruction {
+    VerifyCollectionV1 {
+        authority: *authority,
+        delegate_record: None,
+        metadata: Metadata::find_pda(mint).0,
+        collection_mint: *collection_mint,
+        collection_metadata: Some(Metadata::find_pda(collection_mint).0),
+        collection_master_edition: Some(MasterEdition::find_pda(collection_mint).0),
+        system_program: system_program::id(),
+        sysvar_instructions: sysvar::instructions::id(),
+    }
+    .instruction()
+}
@@ -1,11 +1,28 @@
 pub mod keypair;
 pub mod token;
+pub mod token_2022;
+pub mod metadata;
+pub mod nft;
 pub mod message;
 pub mod transfer;
-pub mod debug;
+pub mod system;
+pub mod instruction;
+pub mod transaction;
+pub mod lookup_table;
+pub mod nonce;
+pub mod stake;
+pub mod idl;
 
 pub use keypair::*;
 pub use token::*;
+pub use token_2022::*;
+pub use nft::*;
 pub use message::*;
 pub use transfer::*;
-pub use debug::*;
+pub use system::*;
+pub use instruction::*;
+pub use transaction::*;
+pub use lookup_table::*;
+pub use nonce::*;
+pub use stake::*;
+pub use idl::*;
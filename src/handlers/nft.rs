@@ -0,0 +1,132 @@
+use axum::response::Json;
+use solana_program::pubkey::Pubkey;
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+use crate::handlers::metadata::{master_edition_instruction, verify_collection_instruction};
+use crate::handlers::token::{build_create_token, build_mint_token};
+use crate::models::{
+    ApiError, CreateTokenRequest, MintTokenRequest, NftMintRequest, NftMintResponse, TokenProgram,
+    TransactionOptions,
+};
+use crate::utils::{ApiJson, InstructionBatch, validate_pubkey, instruction_to_transaction};
+
+/// Accounts created or touched by `/nft/mint`, all derived from the mint.
+pub struct NftAddresses {
+    pub token_account: Pubkey,
+    pub metadata: Pubkey,
+    pub master_edition: Pubkey,
+}
+
+/// Create the mint and its metadata, mint the single token to the owner's
+/// associated token account, then lock the supply with a master edition.
+/// The mint account, mint authority, update authority and (when verifying)
+/// collection authority all sign.
+pub fn build_nft_mint(payload: &NftMintRequest) -> Result<(InstructionBatch, NftAddresses), ApiError> {
+    let mint = validate_pubkey(&payload.mint)
+        .map_err(|e| ApiError::invalid_pubkey("mint", e))?;
+
+    let owner = validate_pubkey(&payload.owner)
+        .map_err(|e| ApiError::invalid_pubkey("owner", e))?;
+
+    let mint_authority = match &payload.mint_authority {
+        Some(mint_authority) => validate_pubkey(mint_authority)
+            .map_err(|e| ApiError::invalid_pubkey("mintAuthority", e))?,
+        None => owner,
+    };
+
+    let update_authority = match &payload.metadata.update_authority {
+        Some(update_authority) => validate_pubkey(update_authority)
+            .map_err(|e| ApiError::invalid_pubkey("metadata.updateAuthority", e))?,
+        None => mint_authority,
+    };
+
+    if payload.metadata.collection.is_some() {
+        return Err(ApiError::invalid_field("metadata.collection", "use \"collection.mint\" instead"));
+    }
+    let collection = payload.collection.as_ref()
+        .map(|collection| -> Result<_, ApiError> {
+            let collection_mint = validate_pubkey(&collection.mint)
+                .map_err(|e| ApiError::invalid_pubkey("collection.mint", e))?;
+            let authority = collection.authority.as_ref()
+                .map(|authority| validate_pubkey(authority)
+                    .map_err(|e| ApiError::invalid_pubkey("collection.authority", e)))
+                .transpose()?;
+            Ok((collection_mint, authority))
+        })
+        .transpose()?;
+
+    let mut metadata = payload.metadata.clone();
+    metadata.collection = payload.collection.as_ref().map(|collection| collection.mint.clone());
+
+    // The freeze authority goes to the master edition along with the mint
+    // authority, so it has to start out as the mint authority
+    let (create_batch, metadata_address) = build_create_token(&CreateTokenRequest {
+        mint_authority: mint_authority.to_string(),
+        mint: payload.mint.clone(),
+        decimals: 0,
+        freeze_authority: Some(mint_authority.to_string()),
+        create_account: true,
+        payer: payload.payer.clone(),
+        extensions: None,
+        metadata: Some(metadata),
+        program: TokenProgram::Token,
+        options: TransactionOptions::default(),
+    })?;
+    let payer = create_batch.default_fee_payer;
+    let metadata_address = metadata_address
+        .ok_or_else(|| ApiError::instruction_build("metadata account was not derived for the mint"))?;
+
+    let token_account = get_associated_token_address(&owner, &mint);
+    let mint_batch = build_mint_token(&MintTokenRequest {
+        mint: payload.mint.clone(),
+        destination: token_account.to_string(),
+        authority: mint_authority.to_string(),
+        amount: 1,
+        multisig_signers: Vec::new(),
+        program: TokenProgram::Token,
+        options: TransactionOptions::default(),
+    })?;
+
+    let (edition_instruction, master_edition) = master_edition_instruction(
+        &mint,
+        &mint_authority,
+        &update_authority,
+        &payer,
+        payload.max_supply.unwrap_or(0),
+    );
+
+    let mut instructions = create_batch.instructions;
+    instructions.push(create_associated_token_account_idempotent(&payer, &owner, &mint, &spl_token::id()));
+    instructions.extend(mint_batch.instructions);
+    instructions.push(edition_instruction);
+    if let Some((collection_mint, Some(authority))) = collection {
+        instructions.push(verify_collection_instruction(&mint, &collection_mint, &authority));
+    }
+
+    Ok((
+        InstructionBatch {
+            instructions,
+            default_fee_payer: payer,
+        },
+        NftAddresses {
+            token_account,
+            metadata: metadata_address,
+            master_edition,
+        },
+    ))
+}
+
+pub async fn nft_mint(
+    ApiJson(payload): ApiJson<NftMintRequest>,
+) -> Result<Json<NftMintResponse>, ApiError> {
+    let (batch, addresses) = build_nft_mint(&payload)?;
+    let transaction = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(NftMintResponse {
+        transaction,
+        token_account: addresses.token_account.to_string(),
+        metadata_address: addresses.metadata.to_string(),
+        master_edition_address: addresses.master_edition.to_string(),
+    }))
+}
@@ -0,0 +1,116 @@
+use axum::response::Json;
+use solana_program::{nonce::State as NonceState, rent::Rent, system_instruction};
+use crate::models::{
+    ApiError, TransactionResponse, CreateNonceAccountRequest, AdvanceNonceRequest,
+    WithdrawNonceRequest, AuthorizeNonceRequest,
+};
+use crate::utils::{ApiJson, InstructionBatch, validate_pubkey, instruction_to_transaction};
+
+pub fn build_create_nonce_account(payload: &CreateNonceAccountRequest) -> Result<InstructionBatch, ApiError> {
+    let from = validate_pubkey(&payload.from)
+        .map_err(|e| ApiError::invalid_pubkey("from", e))?;
+    let nonce_account = validate_pubkey(&payload.nonce_account)
+        .map_err(|e| ApiError::invalid_pubkey("nonceAccount", e))?;
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+
+    // The nonce account must be rent exempt; default to exactly that
+    let minimum_balance = Rent::default().minimum_balance(NonceState::size());
+    let lamports = payload.lamports.unwrap_or(minimum_balance);
+    if lamports < minimum_balance {
+        return Err(ApiError::invalid_field(
+            "lamports",
+            format!("nonce account needs at least {} lamports to be rent exempt", minimum_balance),
+        ));
+    }
+
+    Ok(InstructionBatch {
+        instructions: system_instruction::create_nonce_account(&from, &nonce_account, &authority, lamports),
+        default_fee_payer: from,
+    })
+}
+
+pub fn build_advance_nonce(payload: &AdvanceNonceRequest) -> Result<InstructionBatch, ApiError> {
+    let nonce_account = validate_pubkey(&payload.nonce_account)
+        .map_err(|e| ApiError::invalid_pubkey("nonceAccount", e))?;
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+
+    Ok(InstructionBatch {
+        instructions: vec![system_instruction::advance_nonce_account(&nonce_account, &authority)],
+        default_fee_payer: authority,
+    })
+}
+
+pub fn build_withdraw_nonce(payload: &WithdrawNonceRequest) -> Result<InstructionBatch, ApiError> {
+    let nonce_account = validate_pubkey(&payload.nonce_account)
+        .map_err(|e| ApiError::invalid_pubkey("nonceAccount", e))?;
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+    let to = validate_pubkey(&payload.to)
+        .map_err(|e| ApiError::invalid_pubkey("to", e))?;
+
+    Ok(InstructionBatch {
+        instructions: vec![system_instruction::withdraw_nonce_account(
+            &nonce_account,
+            &authority,
+            &to,
+            payload.lamports,
+        )],
+        default_fee_payer: authority,
+    })
+}
+
+pub fn build_authorize_nonce(payload: &AuthorizeNonceRequest) -> Result<InstructionBatch, ApiError> {
+    let nonce_account = validate_pubkey(&payload.nonce_account)
+        .map_err(|e| ApiError::invalid_pubkey("nonceAccount", e))?;
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+    let new_authority = validate_pubkey(&payload.new_authority)
+        .map_err(|e| ApiError::invalid_pubkey("newAuthority", e))?;
+
+    Ok(InstructionBatch {
+        instructions: vec![system_instruction::authorize_nonce_account(
+            &nonce_account,
+            &authority,
+            &new_authority,
+        )],
+        default_fee_payer: authority,
+    })
+}
+
+pub async fn create_nonce_account(
+    ApiJson(payload): ApiJson<CreateNonceAccountRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_create_nonce_account(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn advance_nonce(
+    ApiJson(payload): ApiJson<AdvanceNonceRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_advance_nonce(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn withdraw_nonce(
+    ApiJson(payload): ApiJson<WithdrawNonceRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_withdraw_nonce(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn authorize_nonce(
+    ApiJson(payload): ApiJson<AuthorizeNonceRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_authorize_nonce(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
@@ -0,0 +1,282 @@
+use axum::response::Json;
+use solana_program::{
+    pubkey::Pubkey,
+    rent::Rent,
+    stake::{
+        self,
+        instruction as stake_instruction,
+        state::{Authorized, Lockup, StakeAuthorize, StakeStateV2},
+    },
+};
+use crate::models::{
+    ApiError, TransactionResponse, CreateStakeAccountRequest, CreateStakeAccountWithSeedRequest,
+    DelegateStakeRequest, DeactivateStakeRequest, WithdrawStakeRequest, SplitStakeRequest,
+    MergeStakeRequest, AuthorizeStakeRequest, StakeAuthorityType, StakeLockupSpec, SeedAccountResponse,
+};
+use crate::utils::{ApiJson, InstructionBatch, validate_pubkey, instruction_to_transaction};
+
+/// New stake accounts must hold at least this much before anything is
+/// delegated, or the stake program rejects them.
+fn check_rent_exempt(field: &str, lamports: u64) -> Result<(), ApiError> {
+    let minimum_balance = Rent::default().minimum_balance(StakeStateV2::size_of());
+    if lamports < minimum_balance {
+        return Err(ApiError::invalid_field(
+            field,
+            format!("stake account needs at least {} lamports to be rent exempt", minimum_balance),
+        ));
+    }
+    Ok(())
+}
+
+fn optional_pubkey(field: &str, value: Option<&String>) -> Result<Option<Pubkey>, ApiError> {
+    value
+        .map(|value| validate_pubkey(value).map_err(|e| ApiError::invalid_pubkey(field, e)))
+        .transpose()
+}
+
+fn parse_authorized(from: Pubkey, staker: Option<&String>, withdrawer: Option<&String>) -> Result<Authorized, ApiError> {
+    Ok(Authorized {
+        staker: optional_pubkey("staker", staker)?.unwrap_or(from),
+        withdrawer: optional_pubkey("withdrawer", withdrawer)?.unwrap_or(from),
+    })
+}
+
+fn parse_lockup(lockup: Option<&StakeLockupSpec>) -> Result<Lockup, ApiError> {
+    let Some(lockup) = lockup else {
+        return Ok(Lockup::default());
+    };
+
+    Ok(Lockup {
+        unix_timestamp: lockup.unix_timestamp,
+        epoch: lockup.epoch,
+        custodian: optional_pubkey("lockup.custodian", lockup.custodian.as_ref())?.unwrap_or_default(),
+    })
+}
+
+pub fn build_create_stake_account(payload: &CreateStakeAccountRequest) -> Result<InstructionBatch, ApiError> {
+    let from = validate_pubkey(&payload.from)
+        .map_err(|e| ApiError::invalid_pubkey("from", e))?;
+    let stake_account = validate_pubkey(&payload.stake_account)
+        .map_err(|e| ApiError::invalid_pubkey("stakeAccount", e))?;
+    let authorized = parse_authorized(from, payload.staker.as_ref(), payload.withdrawer.as_ref())?;
+    let lockup = parse_lockup(payload.lockup.as_ref())?;
+    check_rent_exempt("lamports", payload.lamports)?;
+
+    Ok(InstructionBatch {
+        instructions: stake_instruction::create_account(&from, &stake_account, &authorized, &lockup, payload.lamports),
+        default_fee_payer: from,
+    })
+}
+
+/// Also returns the derived stake account address.
+pub fn build_create_stake_account_with_seed(
+    payload: &CreateStakeAccountWithSeedRequest,
+) -> Result<(InstructionBatch, Pubkey), ApiError> {
+    let from = validate_pubkey(&payload.from)
+        .map_err(|e| ApiError::invalid_pubkey("from", e))?;
+    let base = validate_pubkey(&payload.base)
+        .map_err(|e| ApiError::invalid_pubkey("base", e))?;
+    let stake_account = Pubkey::create_with_seed(&base, &payload.seed, &stake::program::id())
+        .map_err(|e| ApiError::invalid_field("seed", e))?;
+    let authorized = parse_authorized(from, payload.staker.as_ref(), payload.withdrawer.as_ref())?;
+    let lockup = parse_lockup(payload.lockup.as_ref())?;
+    check_rent_exempt("lamports", payload.lamports)?;
+
+    let batch = InstructionBatch {
+        instructions: stake_instruction::create_account_with_seed(
+            &from,
+            &stake_account,
+            &base,
+            &payload.seed,
+            &authorized,
+            &lockup,
+            payload.lamports,
+        ),
+        default_fee_payer: from,
+    };
+
+    Ok((batch, stake_account))
+}
+
+pub fn build_delegate_stake(payload: &DelegateStakeRequest) -> Result<InstructionBatch, ApiError> {
+    let stake_account = validate_pubkey(&payload.stake_account)
+        .map_err(|e| ApiError::invalid_pubkey("stakeAccount", e))?;
+    let staker = validate_pubkey(&payload.staker)
+        .map_err(|e| ApiError::invalid_pubkey("staker", e))?;
+    let vote_account = validate_pubkey(&payload.vote_account)
+        .map_err(|e| ApiError::invalid_pubkey("voteAccount", e))?;
+
+    Ok(InstructionBatch {
+        instructions: vec![stake_instruction::delegate_stake(&stake_account, &staker, &vote_account)],
+        default_fee_payer: staker,
+    })
+}
+
+pub fn build_deactivate_stake(payload: &DeactivateStakeRequest) -> Result<InstructionBatch, ApiError> {
+    let stake_account = validate_pubkey(&payload.stake_account)
+        .map_err(|e| ApiError::invalid_pubkey("stakeAccount", e))?;
+    let staker = validate_pubkey(&payload.staker)
+        .map_err(|e| ApiError::invalid_pubkey("staker", e))?;
+
+    Ok(InstructionBatch {
+        instructions: vec![stake_instruction::deactivate_stake(&stake_account, &staker)],
+        default_fee_payer: staker,
+    })
+}
+
+pub fn build_withdraw_stake(payload: &WithdrawStakeRequest) -> Result<InstructionBatch, ApiError> {
+    let stake_account = validate_pubkey(&payload.stake_account)
+        .map_err(|e| ApiError::invalid_pubkey("stakeAccount", e))?;
+    let withdrawer = validate_pubkey(&payload.withdrawer)
+        .map_err(|e| ApiError::invalid_pubkey("withdrawer", e))?;
+    let to = validate_pubkey(&payload.to)
+        .map_err(|e| ApiError::invalid_pubkey("to", e))?;
+    let custodian = optional_pubkey("custodian", payload.custodian.as_ref())?;
+
+    Ok(InstructionBatch {
+        instructions: vec![stake_instruction::withdraw(
+            &stake_account,
+            &withdrawer,
+            &to,
+            payload.lamports,
+            custodian.as_ref(),
+        )],
+        default_fee_payer: withdrawer,
+    })
+}
+
+/// The new account is allocated and assigned to the stake program here, so
+/// `splitStakeAccount` signs and must not exist yet.
+pub fn build_split_stake(payload: &SplitStakeRequest) -> Result<InstructionBatch, ApiError> {
+    let stake_account = validate_pubkey(&payload.stake_account)
+        .map_err(|e| ApiError::invalid_pubkey("stakeAccount", e))?;
+    let staker = validate_pubkey(&payload.staker)
+        .map_err(|e| ApiError::invalid_pubkey("staker", e))?;
+    let split_stake_account = validate_pubkey(&payload.split_stake_account)
+        .map_err(|e| ApiError::invalid_pubkey("splitStakeAccount", e))?;
+    check_rent_exempt("lamports", payload.lamports)?;
+
+    Ok(InstructionBatch {
+        instructions: stake_instruction::split(&stake_account, &staker, payload.lamports, &split_stake_account),
+        default_fee_payer: staker,
+    })
+}
+
+pub fn build_merge_stake(payload: &MergeStakeRequest) -> Result<InstructionBatch, ApiError> {
+    let stake_account = validate_pubkey(&payload.stake_account)
+        .map_err(|e| ApiError::invalid_pubkey("stakeAccount", e))?;
+    let source = validate_pubkey(&payload.source)
+        .map_err(|e| ApiError::invalid_pubkey("source", e))?;
+    let staker = validate_pubkey(&payload.staker)
+        .map_err(|e| ApiError::invalid_pubkey("staker", e))?;
+
+    if source == stake_account {
+        return Err(ApiError::invalid_field("source", "cannot merge a stake account into itself"));
+    }
+
+    Ok(InstructionBatch {
+        instructions: stake_instruction::merge(&stake_account, &source, &staker),
+        default_fee_payer: staker,
+    })
+}
+
+pub fn build_authorize_stake(payload: &AuthorizeStakeRequest) -> Result<InstructionBatch, ApiError> {
+    let stake_account = validate_pubkey(&payload.stake_account)
+        .map_err(|e| ApiError::invalid_pubkey("stakeAccount", e))?;
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+    let new_authority = validate_pubkey(&payload.new_authority)
+        .map_err(|e| ApiError::invalid_pubkey("newAuthority", e))?;
+    let custodian = optional_pubkey("custodian", payload.custodian.as_ref())?;
+
+    let stake_authorize = match payload.authority_type {
+        StakeAuthorityType::Staker => StakeAuthorize::Staker,
+        StakeAuthorityType::Withdrawer => StakeAuthorize::Withdrawer,
+    };
+
+    Ok(InstructionBatch {
+        instructions: vec![stake_instruction::authorize(
+            &stake_account,
+            &authority,
+            &new_authority,
+            stake_authorize,
+            custodian.as_ref(),
+        )],
+        default_fee_payer: authority,
+    })
+}
+
+pub async fn create_stake_account(
+    ApiJson(payload): ApiJson<CreateStakeAccountRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_create_stake_account(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn create_stake_account_with_seed(
+    ApiJson(payload): ApiJson<CreateStakeAccountWithSeedRequest>,
+) -> Result<Json<SeedAccountResponse>, ApiError> {
+    let (batch, address) = build_create_stake_account_with_seed(&payload)?;
+    let transaction = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(SeedAccountResponse {
+        transaction,
+        address: address.to_string(),
+    }))
+}
+
+pub async fn delegate_stake(
+    ApiJson(payload): ApiJson<DelegateStakeRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_delegate_stake(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn deactivate_stake(
+    ApiJson(payload): ApiJson<DeactivateStakeRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_deactivate_stake(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn withdraw_stake(
+    ApiJson(payload): ApiJson<WithdrawStakeRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_withdraw_stake(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn split_stake(
+    ApiJson(payload): ApiJson<SplitStakeRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_split_stake(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn merge_stake(
+    ApiJson(payload): ApiJson<MergeStakeRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_merge_stake(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn authorize_stake(
+    ApiJson(payload): ApiJson<AuthorizeStakeRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_authorize_stake(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
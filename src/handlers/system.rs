@@ -0,0 +1,248 @@
+use axum::response::Json;
+use solana_program::{
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::{self, MAX_PERMITTED_DATA_LENGTH},
+    system_program,
+};
+use crate::models::{
+    ApiError, TransactionResponse, SeedAccountResponse, CreateAccountRequest, CreateAccountWithSeedRequest,
+    AssignRequest, AssignWithSeedRequest, AllocateRequest, AllocateWithSeedRequest, TransferWithSeedRequest,
+    TransactionOptions,
+};
+use crate::utils::{ApiJson, InstructionBatch, validate_pubkey, instruction_to_transaction};
+
+fn check_space(space: u64) -> Result<(), ApiError> {
+    if space > MAX_PERMITTED_DATA_LENGTH {
+        return Err(ApiError::invalid_field(
+            "space",
+            format!("must not exceed {} bytes", MAX_PERMITTED_DATA_LENGTH),
+        ));
+    }
+    Ok(())
+}
+
+/// New accounts must be rent exempt; default to exactly that.
+fn account_lamports(lamports: Option<u64>, space: u64) -> Result<u64, ApiError> {
+    let minimum_balance = Rent::default().minimum_balance(space as usize);
+    let lamports = lamports.unwrap_or(minimum_balance);
+    if lamports < minimum_balance {
+        return Err(ApiError::invalid_field(
+            "lamports",
+            format!("an account with {} bytes needs at least {} lamports to be rent exempt", space, minimum_balance),
+        ));
+    }
+    Ok(lamports)
+}
+
+/// Same derivation the System program checks for its `*_with_seed`
+/// instructions.
+fn seed_address(base: &Pubkey, seed: &str, owner: &Pubkey) -> Result<Pubkey, ApiError> {
+    Pubkey::create_with_seed(base, seed, owner).map_err(|e| ApiError::invalid_field("seed", e))
+}
+
+pub fn build_create_account(payload: &CreateAccountRequest) -> Result<InstructionBatch, ApiError> {
+    let from = validate_pubkey(&payload.from)
+        .map_err(|e| ApiError::invalid_pubkey("from", e))?;
+    let new_account = validate_pubkey(&payload.new_account)
+        .map_err(|e| ApiError::invalid_pubkey("newAccount", e))?;
+    let owner = validate_pubkey(&payload.owner)
+        .map_err(|e| ApiError::invalid_pubkey("owner", e))?;
+    check_space(payload.space)?;
+    let lamports = account_lamports(payload.lamports, payload.space)?;
+
+    Ok(InstructionBatch {
+        instructions: vec![system_instruction::create_account(&from, &new_account, lamports, payload.space, &owner)],
+        default_fee_payer: from,
+    })
+}
+
+/// Returns the batch together with the derived account address.
+pub fn build_create_account_with_seed(payload: &CreateAccountWithSeedRequest) -> Result<(InstructionBatch, Pubkey), ApiError> {
+    let from = validate_pubkey(&payload.from)
+        .map_err(|e| ApiError::invalid_pubkey("from", e))?;
+    let base = validate_pubkey(&payload.base)
+        .map_err(|e| ApiError::invalid_pubkey("base", e))?;
+    let owner = validate_pubkey(&payload.owner)
+        .map_err(|e| ApiError::invalid_pubkey("owner", e))?;
+    let address = seed_address(&base, &payload.seed, &owner)?;
+    check_space(payload.space)?;
+    let lamports = account_lamports(payload.lamports, payload.space)?;
+
+    Ok((
+        InstructionBatch {
+            instructions: vec![system_instruction::create_account_with_seed(
+                &from,
+                &address,
+                &base,
+                &payload.seed,
+                lamports,
+                payload.space,
+                &owner,
+            )],
+            default_fee_payer: from,
+        },
+        address,
+    ))
+}
+
+pub fn build_assign(payload: &AssignRequest) -> Result<InstructionBatch, ApiError> {
+    let account = validate_pubkey(&payload.account)
+        .map_err(|e| ApiError::invalid_pubkey("account", e))?;
+    let owner = validate_pubkey(&payload.owner)
+        .map_err(|e| ApiError::invalid_pubkey("owner", e))?;
+
+    Ok(InstructionBatch {
+        instructions: vec![system_instruction::assign(&account, &owner)],
+        default_fee_payer: account,
+    })
+}
+
+/// Returns the batch together with the derived account address.
+pub fn build_assign_with_seed(payload: &AssignWithSeedRequest) -> Result<(InstructionBatch, Pubkey), ApiError> {
+    let base = validate_pubkey(&payload.base)
+        .map_err(|e| ApiError::invalid_pubkey("base", e))?;
+    let owner = validate_pubkey(&payload.owner)
+        .map_err(|e| ApiError::invalid_pubkey("owner", e))?;
+    let address = seed_address(&base, &payload.seed, &owner)?;
+
+    Ok((
+        InstructionBatch {
+            instructions: vec![system_instruction::assign_with_seed(&address, &base, &payload.seed, &owner)],
+            default_fee_payer: base,
+        },
+        address,
+    ))
+}
+
+pub fn build_allocate(payload: &AllocateRequest) -> Result<InstructionBatch, ApiError> {
+    let account = validate_pubkey(&payload.account)
+        .map_err(|e| ApiError::invalid_pubkey("account", e))?;
+    check_space(payload.space)?;
+
+    Ok(InstructionBatch {
+        instructions: vec![system_instruction::allocate(&account, payload.space)],
+        default_fee_payer: account,
+    })
+}
+
+/// Returns the batch together with the derived account address.
+pub fn build_allocate_with_seed(payload: &AllocateWithSeedRequest) -> Result<(InstructionBatch, Pubkey), ApiError> {
+    let base = validate_pubkey(&payload.base)
+        .map_err(|e| ApiError::invalid_pubkey("base", e))?;
+    let owner = validate_pubkey(&payload.owner)
+        .map_err(|e| ApiError::invalid_pubkey("owner", e))?;
+    let address = seed_address(&base, &payload.seed, &owner)?;
+    check_space(payload.space)?;
+
+    Ok((
+        InstructionBatch {
+            instructions: vec![system_instruction::allocate_with_seed(
+                &address,
+                &base,
+                &payload.seed,
+                payload.space,
+                &owner,
+            )],
+            default_fee_payer: base,
+        },
+        address,
+    ))
+}
+
+/// Returns the batch together with the derived source address.
+pub fn build_transfer_with_seed(payload: &TransferWithSeedRequest) -> Result<(InstructionBatch, Pubkey), ApiError> {
+    let base = validate_pubkey(&payload.base)
+        .map_err(|e| ApiError::invalid_pubkey("base", e))?;
+    let from_owner = match &payload.from_owner {
+        Some(from_owner) => validate_pubkey(from_owner)
+            .map_err(|e| ApiError::invalid_pubkey("fromOwner", e))?,
+        None => system_program::id(),
+    };
+    let to = validate_pubkey(&payload.to)
+        .map_err(|e| ApiError::invalid_pubkey("to", e))?;
+    let address = seed_address(&base, &payload.seed, &from_owner)?;
+
+    Ok((
+        InstructionBatch {
+            instructions: vec![system_instruction::transfer_with_seed(
+                &address,
+                &base,
+                payload.seed.clone(),
+                &from_owner,
+                &to,
+                payload.lamports,
+            )],
+            default_fee_payer: base,
+        },
+        address,
+    ))
+}
+
+fn seed_account_response(
+    batch: InstructionBatch,
+    address: Pubkey,
+    options: &TransactionOptions,
+) -> Result<Json<SeedAccountResponse>, ApiError> {
+    let transaction = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, options)?;
+
+    Ok(Json(SeedAccountResponse {
+        transaction,
+        address: address.to_string(),
+    }))
+}
+
+pub async fn create_account(
+    ApiJson(payload): ApiJson<CreateAccountRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_create_account(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn create_account_with_seed(
+    ApiJson(payload): ApiJson<CreateAccountWithSeedRequest>,
+) -> Result<Json<SeedAccountResponse>, ApiError> {
+    let (batch, address) = build_create_account_with_seed(&payload)?;
+    seed_account_response(batch, address, &payload.options)
+}
+
+pub async fn assign(
+    ApiJson(payload): ApiJson<AssignRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_assign(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn assign_with_seed(
+    ApiJson(payload): ApiJson<AssignWithSeedRequest>,
+) -> Result<Json<SeedAccountResponse>, ApiError> {
+    let (batch, address) = build_assign_with_seed(&payload)?;
+    seed_account_response(batch, address, &payload.options)
+}
+
+pub async fn allocate(
+    ApiJson(payload): ApiJson<AllocateRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_allocate(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn allocate_with_seed(
+    ApiJson(payload): ApiJson<AllocateWithSeedRequest>,
+) -> Result<Json<SeedAccountResponse>, ApiError> {
+    let (batch, address) = build_allocate_with_seed(&payload)?;
+    seed_account_response(batch, address, &payload.options)
+}
+
+pub async fn transfer_with_seed(
+    ApiJson(payload): ApiJson<TransferWithSeedRequest>,
+) -> Result<Json<SeedAccountResponse>, ApiError> {
+    let (batch, address) = build_transfer_with_seed(&payload)?;
+    seed_account_response(batch, address, &payload.options)
+}
@@ -1,95 +1,641 @@
-use axum::{extract::Json as ExtractJson, response::Json, http::StatusCode};
-use spl_token::instruction;
-use crate::models::{TransactionResponse, CreateTokenRequest, MintTokenRequest};
-use crate::utils::{validate_pubkey, instruction_to_transaction};
+use axum::response::Json;
+use solana_program::{
+    instruction::Instruction, program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, rent::Rent,
+    system_instruction,
+};
+use spl_token_2022::{
+    extension::ExtensionType,
+    instruction::{self, AuthorityType, MAX_SIGNERS, MIN_SIGNERS},
+    state::{Mint, Multisig},
+};
+use crate::models::{
+    ApiError, TransactionResponse, CreateTokenRequest, CreateTokenResponse, MintTokenRequest, CreateMultisigRequest, BurnTokenRequest,
+    ApproveTokenRequest, RevokeTokenRequest, FreezeTokenRequest, CloseTokenAccountRequest,
+    SyncNativeRequest, SetAuthorityRequest, TokenAuthorityType, TokenProgram,
+};
+use crate::handlers::metadata::metaplex_metadata_instruction;
+use crate::handlers::token_2022::{mint_extension_instructions, token_metadata_instruction};
+use crate::utils::{ApiJson, InstructionBatch, validate_pubkey, instruction_to_transaction};
 
-pub async fn create_token(
-    ExtractJson(payload): ExtractJson<CreateTokenRequest>,
-) -> Result<Json<TransactionResponse>, StatusCode> {
-    let mint_authority = match validate_pubkey(&payload.mint_authority) {
-        Ok(pubkey) => pubkey,
-        Err(e) => {
-            eprintln!("Invalid mint authority pubkey: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    };
+/// The spl-token-2022 instruction builders also accept the original token
+/// program's id for instructions both programs share.
+pub(crate) fn token_program_id(program: TokenProgram) -> Pubkey {
+    match program {
+        TokenProgram::Token => spl_token::id(),
+        TokenProgram::Token2022 => spl_token_2022::id(),
+    }
+}
 
-    let mint = match validate_pubkey(&payload.mint) {
-        Ok(pubkey) => pubkey,
-        Err(e) => {
-            eprintln!("Invalid mint pubkey: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    };
+/// Signers of a multisig authority. The multisig account itself never
+/// signs, so when signers are given the first one pays by default.
+pub(crate) fn parse_multisig_signers(signers: &[String]) -> Result<Vec<Pubkey>, ApiError> {
+    if signers.len() > MAX_SIGNERS {
+        return Err(ApiError::invalid_field(
+            "multisigSigners",
+            format!("at most {} signers are allowed", MAX_SIGNERS),
+        ));
+    }
 
-    let instruction = instruction::initialize_mint(
-        &spl_token::id(),
-        &mint,
-        &mint_authority,
-        None,
-        payload.decimals,
-    ).map_err(|e| {
-        eprintln!("Failed to create initialize mint instruction: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
-
-    let response_data = match instruction_to_transaction(instruction, Some(mint_authority)) {
-        Ok(transaction) => transaction,
-        Err(e) => {
-            eprintln!("Failed to create transaction: {}", e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    signers
+        .iter()
+        .enumerate()
+        .map(|(index, signer)| {
+            validate_pubkey(signer)
+                .map_err(|e| ApiError::invalid_pubkey(format!("multisigSigners[{}]", index), e))
+        })
+        .collect()
+}
 
-    Ok(Json(response_data))
+pub(crate) fn default_fee_payer(authority: Pubkey, multisig_signers: &[Pubkey]) -> Pubkey {
+    multisig_signers.first().copied().unwrap_or(authority)
 }
 
-pub async fn mint_token(
-    ExtractJson(payload): ExtractJson<MintTokenRequest>,
-) -> Result<Json<TransactionResponse>, StatusCode> {
-    let mint = match validate_pubkey(&payload.mint) {
-        Ok(pubkey) => pubkey,
-        Err(e) => {
-            eprintln!("Invalid mint pubkey: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
-        }
+/// Returns the batch together with the metadata account, when `metadata`
+/// was requested.
+pub fn build_create_token(payload: &CreateTokenRequest) -> Result<(InstructionBatch, Option<Pubkey>), ApiError> {
+    let program_id = token_program_id(payload.program);
+
+    let mint_authority = validate_pubkey(&payload.mint_authority)
+        .map_err(|e| ApiError::invalid_pubkey("mintAuthority", e))?;
+
+    let mint = validate_pubkey(&payload.mint)
+        .map_err(|e| ApiError::invalid_pubkey("mint", e))?;
+
+    let freeze_authority = payload.freeze_authority.as_ref()
+        .map(|freeze_authority| validate_pubkey(freeze_authority)
+            .map_err(|e| ApiError::invalid_pubkey("freezeAuthority", e)))
+        .transpose()?;
+
+    if payload.payer.is_some() && !payload.create_account && payload.metadata.is_none() {
+        return Err(ApiError::invalid_field(
+            "payer",
+            "only used together with \"createAccount\": true or metadata",
+        ));
+    }
+    let payer = match &payload.payer {
+        Some(payer) => validate_pubkey(payer).map_err(|e| ApiError::invalid_pubkey("payer", e))?,
+        None => mint_authority,
     };
 
-    let destination = match validate_pubkey(&payload.destination) {
-        Ok(pubkey) => pubkey,
-        Err(e) => {
-            eprintln!("Invalid destination pubkey: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
+    let (mut instructions, extension_types) = match &payload.extensions {
+        Some(extensions) => {
+            if payload.program != TokenProgram::Token2022 {
+                return Err(ApiError::invalid_field("extensions", "mint extensions require \"program\": \"token-2022\""));
+            }
+            mint_extension_instructions(&program_id, &mint, extensions, freeze_authority.as_ref())?
         }
+        None => (Vec::new(), Vec::new()),
     };
 
-    let authority = match validate_pubkey(&payload.authority) {
-        Ok(pubkey) => pubkey,
-        Err(e) => {
-            eprintln!("Invalid authority pubkey: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
+    // Metadata is written once the mint is initialized
+    let mut metadata_instruction = None;
+    let mut metadata_address = None;
+    let mut metadata_size = 0;
+    if let Some(metadata) = &payload.metadata {
+        let update_authority = match &metadata.update_authority {
+            Some(update_authority) => validate_pubkey(update_authority)
+                .map_err(|e| ApiError::invalid_pubkey("metadata.updateAuthority", e))?,
+            None => mint_authority,
+        };
+
+        match payload.program {
+            TokenProgram::Token => {
+                let (instruction, address) = metaplex_metadata_instruction(
+                    "metadata",
+                    metadata,
+                    &mint,
+                    &mint_authority,
+                    &payer,
+                    &update_authority,
+                )?;
+                metadata_instruction = Some(instruction);
+                metadata_address = Some(address);
+            }
+            TokenProgram::Token2022 => {
+                let points_to_mint = payload.extensions.as_ref()
+                    .and_then(|extensions| extensions.metadata_pointer.as_ref())
+                    .and_then(|pointer| pointer.metadata_address.as_deref())
+                    .is_some_and(|address| validate_pubkey(address).is_ok_and(|address| address == mint));
+                if !points_to_mint {
+                    return Err(ApiError::invalid_field(
+                        "metadata",
+                        "Token-2022 metadata is stored in the mint; set extensions.metadataPointer.metadataAddress to the mint",
+                    ));
+                }
+
+                let (instruction, size) = token_metadata_instruction(
+                    "metadata",
+                    metadata,
+                    &mint,
+                    &mint_authority,
+                    &update_authority,
+                )?;
+                metadata_instruction = Some(instruction);
+                metadata_address = Some(mint);
+                metadata_size = size;
+            }
         }
-    };
+    }
+
+    if payload.create_account {
+        // Each extension adds to the mint account's size, and so to its rent.
+        // Token-2022 metadata is appended later but must be paid for now.
+        // initialize_mint2 doesn't need the rent sysvar account, so the mint
+        // can be created and initialized in the same transaction.
+        let space = ExtensionType::try_calculate_account_len::<Mint>(&extension_types)
+            .map_err(ApiError::instruction_build)?;
+        let lamports = Rent::default().minimum_balance(space + metadata_size);
+        instructions.insert(0, system_instruction::create_account(&payer, &mint, lamports, space as u64, &program_id));
+        instructions.push(instruction::initialize_mint2(
+            &program_id,
+            &mint,
+            &mint_authority,
+            freeze_authority.as_ref(),
+            payload.decimals,
+        ).map_err(ApiError::instruction_build)?);
+    } else {
+        instructions.push(instruction::initialize_mint(
+            &program_id,
+            &mint,
+            &mint_authority,
+            freeze_authority.as_ref(),
+            payload.decimals,
+        ).map_err(ApiError::instruction_build)?);
+    }
+    instructions.extend(metadata_instruction);
+
+    Ok((
+        InstructionBatch {
+            instructions,
+            default_fee_payer: payer,
+        },
+        metadata_address,
+    ))
+}
+
+pub fn build_mint_token(payload: &MintTokenRequest) -> Result<InstructionBatch, ApiError> {
+    let program_id = token_program_id(payload.program);
+
+    let mint = validate_pubkey(&payload.mint)
+        .map_err(|e| ApiError::invalid_pubkey("mint", e))?;
+
+    let destination = validate_pubkey(&payload.destination)
+        .map_err(|e| ApiError::invalid_pubkey("destination", e))?;
+
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+
+    let signers = parse_multisig_signers(&payload.multisig_signers)?;
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
 
     let instruction = instruction::mint_to(
-        &spl_token::id(),
+        &program_id,
         &mint,
         &destination,
         &authority,
-        &[],
+        &signer_refs,
         payload.amount,
-    ).map_err(|e| {
-        eprintln!("Failed to create mint to instruction: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
-
-    let response_data = match instruction_to_transaction(instruction, Some(authority)) {
-        Ok(transaction) => transaction,
-        Err(e) => {
-            eprintln!("Failed to create transaction: {}", e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    ).map_err(ApiError::instruction_build)?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction],
+        default_fee_payer: default_fee_payer(authority, &signers),
+    })
+}
+
+pub fn build_create_multisig(payload: &CreateMultisigRequest) -> Result<InstructionBatch, ApiError> {
+    let program_id = token_program_id(payload.program);
+
+    let multisig = validate_pubkey(&payload.multisig)
+        .map_err(|e| ApiError::invalid_pubkey("multisig", e))?;
+
+    let payer = validate_pubkey(&payload.payer)
+        .map_err(|e| ApiError::invalid_pubkey("payer", e))?;
+
+    if !(MIN_SIGNERS..=MAX_SIGNERS).contains(&payload.signers.len()) {
+        return Err(ApiError::invalid_field(
+            "signers",
+            format!("between {} and {} signers are required", MIN_SIGNERS, MAX_SIGNERS),
+        ));
+    }
+
+    let signers = payload.signers
+        .iter()
+        .enumerate()
+        .map(|(index, signer)| {
+            validate_pubkey(signer)
+                .map_err(|e| ApiError::invalid_pubkey(format!("signers[{}]", index), e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+
+    if payload.m == 0 || payload.m as usize > signers.len() {
+        return Err(ApiError::invalid_field(
+            "m",
+            format!("must be between 1 and the number of signers ({})", signers.len()),
+        ));
+    }
+
+    let lamports = Rent::default().minimum_balance(Multisig::LEN);
+    let instructions = vec![
+        system_instruction::create_account(&payer, &multisig, lamports, Multisig::LEN as u64, &program_id),
+        instruction::initialize_multisig2(
+            &program_id,
+            &multisig,
+            &signer_refs,
+            payload.m,
+        ).map_err(ApiError::instruction_build)?,
+    ];
+
+    Ok(InstructionBatch {
+        instructions,
+        default_fee_payer: payer,
+    })
+}
+
+pub fn build_burn_token(payload: &BurnTokenRequest) -> Result<InstructionBatch, ApiError> {
+    let program_id = token_program_id(payload.program);
+
+    let account = validate_pubkey(&payload.account)
+        .map_err(|e| ApiError::invalid_pubkey("account", e))?;
+
+    let mint = validate_pubkey(&payload.mint)
+        .map_err(|e| ApiError::invalid_pubkey("mint", e))?;
+
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+
+    let signers = parse_multisig_signers(&payload.multisig_signers)?;
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+
+    let instruction = match payload.decimals {
+        Some(decimals) => instruction::burn_checked(
+            &program_id,
+            &account,
+            &mint,
+            &authority,
+            &signer_refs,
+            payload.amount,
+            decimals,
+        ),
+        None => instruction::burn(
+            &program_id,
+            &account,
+            &mint,
+            &authority,
+            &signer_refs,
+            payload.amount,
+        ),
+    }.map_err(ApiError::instruction_build)?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction],
+        default_fee_payer: default_fee_payer(authority, &signers),
+    })
+}
+
+pub fn build_approve_token(payload: &ApproveTokenRequest) -> Result<InstructionBatch, ApiError> {
+    let program_id = token_program_id(payload.program);
+
+    let source = validate_pubkey(&payload.source)
+        .map_err(|e| ApiError::invalid_pubkey("source", e))?;
+
+    let delegate = validate_pubkey(&payload.delegate)
+        .map_err(|e| ApiError::invalid_pubkey("delegate", e))?;
+
+    let owner = validate_pubkey(&payload.owner)
+        .map_err(|e| ApiError::invalid_pubkey("owner", e))?;
+
+    let signers = parse_multisig_signers(&payload.multisig_signers)?;
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+
+    let instruction = match (&payload.mint, payload.decimals) {
+        (Some(mint), Some(decimals)) => {
+            let mint = validate_pubkey(mint)
+                .map_err(|e| ApiError::invalid_pubkey("mint", e))?;
+
+            instruction::approve_checked(
+                &program_id,
+                &source,
+                &mint,
+                &delegate,
+                &owner,
+                &signer_refs,
+                payload.amount,
+                decimals,
+            )
+        }
+        (None, None) => instruction::approve(
+            &program_id,
+            &source,
+            &delegate,
+            &owner,
+            &signer_refs,
+            payload.amount,
+        ),
+        (Some(_), None) => return Err(ApiError::invalid_field("decimals", "required when mint is given")),
+        (None, Some(_)) => return Err(ApiError::invalid_field("mint", "required when decimals is given")),
+    }.map_err(ApiError::instruction_build)?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction],
+        default_fee_payer: default_fee_payer(owner, &signers),
+    })
+}
+
+pub fn build_revoke_token(payload: &RevokeTokenRequest) -> Result<InstructionBatch, ApiError> {
+    let program_id = token_program_id(payload.program);
+
+    let source = validate_pubkey(&payload.source)
+        .map_err(|e| ApiError::invalid_pubkey("source", e))?;
+
+    let owner = validate_pubkey(&payload.owner)
+        .map_err(|e| ApiError::invalid_pubkey("owner", e))?;
+
+    let signers = parse_multisig_signers(&payload.multisig_signers)?;
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+
+    let instruction = instruction::revoke(
+        &program_id,
+        &source,
+        &owner,
+        &signer_refs,
+    ).map_err(ApiError::instruction_build)?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction],
+        default_fee_payer: default_fee_payer(owner, &signers),
+    })
+}
+
+pub fn build_freeze_token(payload: &FreezeTokenRequest) -> Result<InstructionBatch, ApiError> {
+    build_freeze_or_thaw(payload, instruction::freeze_account)
+}
+
+pub fn build_thaw_token(payload: &FreezeTokenRequest) -> Result<InstructionBatch, ApiError> {
+    build_freeze_or_thaw(payload, instruction::thaw_account)
+}
+
+/// Shared signature of `freeze_account` and `thaw_account`.
+type FreezeInstructionFn =
+    fn(&Pubkey, &Pubkey, &Pubkey, &Pubkey, &[&Pubkey]) -> Result<Instruction, ProgramError>;
+
+fn build_freeze_or_thaw(
+    payload: &FreezeTokenRequest,
+    build: FreezeInstructionFn,
+) -> Result<InstructionBatch, ApiError> {
+    let program_id = token_program_id(payload.program);
+
+    let account = validate_pubkey(&payload.account)
+        .map_err(|e| ApiError::invalid_pubkey("account", e))?;
+
+    let mint = validate_pubkey(&payload.mint)
+        .map_err(|e| ApiError::invalid_pubkey("mint", e))?;
+
+    let freeze_authority = validate_pubkey(&payload.freeze_authority)
+        .map_err(|e| ApiError::invalid_pubkey("freezeAuthority", e))?;
+
+    let signers = parse_multisig_signers(&payload.multisig_signers)?;
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+
+    let instruction = build(
+        &program_id,
+        &account,
+        &mint,
+        &freeze_authority,
+        &signer_refs,
+    ).map_err(ApiError::instruction_build)?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction],
+        default_fee_payer: default_fee_payer(freeze_authority, &signers),
+    })
+}
+
+pub fn build_close_token_account(payload: &CloseTokenAccountRequest) -> Result<InstructionBatch, ApiError> {
+    let program_id = token_program_id(payload.program);
+
+    let account = validate_pubkey(&payload.account)
+        .map_err(|e| ApiError::invalid_pubkey("account", e))?;
+
+    let destination = validate_pubkey(&payload.destination)
+        .map_err(|e| ApiError::invalid_pubkey("destination", e))?;
+
+    let owner = validate_pubkey(&payload.owner)
+        .map_err(|e| ApiError::invalid_pubkey("owner", e))?;
+
+    let signers = parse_multisig_signers(&payload.multisig_signers)?;
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+
+    let instruction = instruction::close_account(
+        &program_id,
+        &account,
+        &destination,
+        &owner,
+        &signer_refs,
+    ).map_err(ApiError::instruction_build)?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction],
+        default_fee_payer: default_fee_payer(owner, &signers),
+    })
+}
+
+pub fn build_sync_native(payload: &SyncNativeRequest) -> Result<InstructionBatch, ApiError> {
+    let program_id = token_program_id(payload.program);
+
+    let account = validate_pubkey(&payload.account)
+        .map_err(|e| ApiError::invalid_pubkey("account", e))?;
+
+    let payer = validate_pubkey(&payload.payer)
+        .map_err(|e| ApiError::invalid_pubkey("payer", e))?;
+
+    let instruction = instruction::sync_native(&program_id, &account)
+        .map_err(ApiError::instruction_build)?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction],
+        default_fee_payer: payer,
+    })
+}
+
+pub fn build_set_authority(payload: &SetAuthorityRequest) -> Result<InstructionBatch, ApiError> {
+    let program_id = token_program_id(payload.program);
+
+    let account = validate_pubkey(&payload.account)
+        .map_err(|e| ApiError::invalid_pubkey("account", e))?;
+
+    let current_authority = validate_pubkey(&payload.current_authority)
+        .map_err(|e| ApiError::invalid_pubkey("currentAuthority", e))?;
+
+    let new_authority = match &payload.new_authority {
+        Some(new_authority) => {
+            if payload.confirm_revoke {
+                return Err(ApiError::invalid_field(
+                    "confirmRevoke",
+                    "only applies when newAuthority is omitted",
+                ));
+            }
+            Some(validate_pubkey(new_authority)
+                .map_err(|e| ApiError::invalid_pubkey("newAuthority", e))?)
         }
+        None => {
+            if let TokenAuthorityType::AccountOwner = payload.authority_type {
+                return Err(ApiError::invalid_field("newAuthority", "a token account must always have an owner"));
+            }
+            // Revoking is irreversible (e.g. no more tokens can ever be minted)
+            if !payload.confirm_revoke {
+                return Err(ApiError::invalid_field(
+                    "newAuthority",
+                    "omitting newAuthority permanently removes this authority; set \"confirmRevoke\": true to proceed",
+                ));
+            }
+            None
+        }
+    };
+
+    let authority_type = match payload.authority_type {
+        TokenAuthorityType::MintTokens => AuthorityType::MintTokens,
+        TokenAuthorityType::FreezeAccount => AuthorityType::FreezeAccount,
+        TokenAuthorityType::AccountOwner => AuthorityType::AccountOwner,
+        TokenAuthorityType::CloseAccount => AuthorityType::CloseAccount,
+        TokenAuthorityType::TransferFeeConfig => AuthorityType::TransferFeeConfig,
+        TokenAuthorityType::WithheldWithdraw => AuthorityType::WithheldWithdraw,
+        TokenAuthorityType::CloseMint => AuthorityType::CloseMint,
+        TokenAuthorityType::InterestRate => AuthorityType::InterestRate,
+        TokenAuthorityType::PermanentDelegate => AuthorityType::PermanentDelegate,
+        TokenAuthorityType::MetadataPointer => AuthorityType::MetadataPointer,
     };
+    let extension_authority = !matches!(
+        payload.authority_type,
+        TokenAuthorityType::MintTokens
+            | TokenAuthorityType::FreezeAccount
+            | TokenAuthorityType::AccountOwner
+            | TokenAuthorityType::CloseAccount
+    );
+    if extension_authority && payload.program != TokenProgram::Token2022 {
+        return Err(ApiError::invalid_field(
+            "authorityType",
+            "extension authorities require \"program\": \"token-2022\"",
+        ));
+    }
+
+    let signers = parse_multisig_signers(&payload.multisig_signers)?;
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+
+    let instruction = instruction::set_authority(
+        &program_id,
+        &account,
+        new_authority.as_ref(),
+        authority_type,
+        &current_authority,
+        &signer_refs,
+    ).map_err(ApiError::instruction_build)?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction],
+        default_fee_payer: default_fee_payer(current_authority, &signers),
+    })
+}
+
+pub async fn create_token(
+    ApiJson(payload): ApiJson<CreateTokenRequest>,
+) -> Result<Json<CreateTokenResponse>, ApiError> {
+    let (batch, metadata_address) = build_create_token(&payload)?;
+    let transaction = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(CreateTokenResponse {
+        transaction,
+        metadata_address: metadata_address.map(|address| address.to_string()),
+    }))
+}
+
+pub async fn mint_token(
+    ApiJson(payload): ApiJson<MintTokenRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_mint_token(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn create_multisig(
+    ApiJson(payload): ApiJson<CreateMultisigRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_create_multisig(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn burn_token(
+    ApiJson(payload): ApiJson<BurnTokenRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_burn_token(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn approve_token(
+    ApiJson(payload): ApiJson<ApproveTokenRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_approve_token(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn revoke_token(
+    ApiJson(payload): ApiJson<RevokeTokenRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_revoke_token(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn freeze_token(
+    ApiJson(payload): ApiJson<FreezeTokenRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_freeze_token(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn thaw_token(
+    ApiJson(payload): ApiJson<FreezeTokenRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_thaw_token(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn close_token_account(
+    ApiJson(payload): ApiJson<CloseTokenAccountRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_close_token_account(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn sync_native(
+    ApiJson(payload): ApiJson<SyncNativeRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_sync_native(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn set_authority(
+    ApiJson(payload): ApiJson<SetAuthorityRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_set_authority(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
 
     Ok(Json(response_data))
 }
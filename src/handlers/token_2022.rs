@@ -0,0 +1,431 @@
+use axum::response::Json;
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id, instruction::create_associated_token_account_idempotent,
+};
+use spl_token_2022::{
+    extension::{
+        default_account_state, interest_bearing_mint, metadata_pointer,
+        transfer_fee::{self, TransferFee},
+        ExtensionType,
+    },
+    instruction,
+    state::AccountState,
+};
+use spl_token_metadata_interface::state::TokenMetadata;
+use crate::handlers::metadata::validate_metadata_fields;
+use crate::handlers::token::{default_fee_payer, parse_multisig_signers};
+use crate::models::{
+    ApiError, DefaultAccountState, MintExtensions, TokenMetadataSpec, TransactionResponse, TransferWithFeeRequest,
+    TransferWithFeeResponse, WithdrawWithheldRequest, HarvestWithheldRequest, SetTransferFeeRequest,
+};
+use crate::utils::{ApiJson, InstructionBatch, validate_pubkey, instruction_to_transaction};
+
+fn optional_pubkey(field: &str, value: &Option<String>) -> Result<Option<Pubkey>, ApiError> {
+    value
+        .as_ref()
+        .map(|value| validate_pubkey(value).map_err(|e| ApiError::invalid_pubkey(field, e)))
+        .transpose()
+}
+
+fn validate_basis_points(field: &str, basis_points: u16) -> Result<(), ApiError> {
+    if basis_points > transfer_fee::MAX_FEE_BASIS_POINTS {
+        return Err(ApiError::invalid_field(
+            field,
+            format!("must not exceed {}", transfer_fee::MAX_FEE_BASIS_POINTS),
+        ));
+    }
+    Ok(())
+}
+
+fn parse_sources(sources: &[String]) -> Result<Vec<Pubkey>, ApiError> {
+    if sources.is_empty() {
+        return Err(ApiError::invalid_field("sources", "at least one token account is required"));
+    }
+
+    sources
+        .iter()
+        .enumerate()
+        .map(|(index, source)| {
+            validate_pubkey(source).map_err(|e| ApiError::invalid_pubkey(format!("sources[{}]", index), e))
+        })
+        .collect()
+}
+
+/// Instructions that initialize the requested mint extensions, plus the
+/// extension types needed to size the mint account. Every one of them has to
+/// run before `initialize_mint`.
+pub(crate) fn mint_extension_instructions(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    extensions: &MintExtensions,
+    freeze_authority: Option<&Pubkey>,
+) -> Result<(Vec<Instruction>, Vec<ExtensionType>), ApiError> {
+    let mut instructions = Vec::new();
+    let mut extension_types = Vec::new();
+
+    if let Some(transfer_fee) = &extensions.transfer_fee {
+        validate_basis_points("extensions.transferFee.basisPoints", transfer_fee.basis_points)?;
+        let config_authority = optional_pubkey("extensions.transferFee.configAuthority", &transfer_fee.config_authority)?;
+        let withdraw_authority = optional_pubkey("extensions.transferFee.withdrawAuthority", &transfer_fee.withdraw_authority)?;
+
+        instructions.push(transfer_fee::instruction::initialize_transfer_fee_config(
+            program_id,
+            mint,
+            config_authority.as_ref(),
+            withdraw_authority.as_ref(),
+            transfer_fee.basis_points,
+            transfer_fee.maximum_fee,
+        ).map_err(ApiError::instruction_build)?);
+        extension_types.push(ExtensionType::TransferFeeConfig);
+    }
+
+    if let Some(close_authority) = optional_pubkey("extensions.closeAuthority", &extensions.close_authority)? {
+        instructions.push(instruction::initialize_mint_close_authority(program_id, mint, Some(&close_authority))
+            .map_err(ApiError::instruction_build)?);
+        extension_types.push(ExtensionType::MintCloseAuthority);
+    }
+
+    if extensions.non_transferable {
+        instructions.push(instruction::initialize_non_transferable_mint(program_id, mint)
+            .map_err(ApiError::instruction_build)?);
+        extension_types.push(ExtensionType::NonTransferable);
+    }
+
+    if let Some(interest_bearing) = &extensions.interest_bearing {
+        let rate_authority = optional_pubkey("extensions.interestBearing.rateAuthority", &interest_bearing.rate_authority)?;
+
+        instructions.push(interest_bearing_mint::instruction::initialize(
+            program_id,
+            mint,
+            rate_authority,
+            interest_bearing.rate,
+        ).map_err(ApiError::instruction_build)?);
+        extension_types.push(ExtensionType::InterestBearingConfig);
+    }
+
+    if let Some(state) = extensions.default_account_state {
+        let state = match state {
+            DefaultAccountState::Initialized => AccountState::Initialized,
+            DefaultAccountState::Frozen => {
+                // Nobody could ever thaw the accounts otherwise
+                if freeze_authority.is_none() {
+                    return Err(ApiError::invalid_field(
+                        "extensions.defaultAccountState",
+                        "\"frozen\" requires a freezeAuthority",
+                    ));
+                }
+                AccountState::Frozen
+            }
+        };
+
+        instructions.push(default_account_state::instruction::initialize_default_account_state(program_id, mint, &state)
+            .map_err(ApiError::instruction_build)?);
+        extension_types.push(ExtensionType::DefaultAccountState);
+    }
+
+    if let Some(delegate) = optional_pubkey("extensions.permanentDelegate", &extensions.permanent_delegate)? {
+        instructions.push(instruction::initialize_permanent_delegate(program_id, mint, &delegate)
+            .map_err(ApiError::instruction_build)?);
+        extension_types.push(ExtensionType::PermanentDelegate);
+    }
+
+    if let Some(pointer) = &extensions.metadata_pointer {
+        let authority = optional_pubkey("extensions.metadataPointer.authority", &pointer.authority)?;
+        let metadata_address = optional_pubkey("extensions.metadataPointer.metadataAddress", &pointer.metadata_address)?;
+        if authority.is_none() && metadata_address.is_none() {
+            return Err(ApiError::invalid_field(
+                "extensions.metadataPointer",
+                "at least one of authority or metadataAddress is required",
+            ));
+        }
+
+        instructions.push(metadata_pointer::instruction::initialize(program_id, mint, authority, metadata_address)
+            .map_err(ApiError::instruction_build)?);
+        extension_types.push(ExtensionType::MetadataPointer);
+    }
+
+    Ok((instructions, extension_types))
+}
+
+/// Token metadata `initialize` storing the metadata in the mint account
+/// itself, plus the number of bytes it adds to the mint. The program grows
+/// the account but doesn't fund it, so the mint needs that much more rent up
+/// front. Runs after the mint is initialized.
+pub(crate) fn token_metadata_instruction(
+    field: &str,
+    metadata: &TokenMetadataSpec,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    update_authority: &Pubkey,
+) -> Result<(Instruction, usize), ApiError> {
+    validate_metadata_fields(field, metadata)?;
+    if metadata.seller_fee_basis_points != 0
+        || !metadata.creators.is_empty()
+        || metadata.is_mutable.is_some()
+        || metadata.collection.is_some()
+    {
+        return Err(ApiError::invalid_field(
+            field,
+            "sellerFeeBasisPoints, creators, isMutable and collection are only supported for Metaplex metadata",
+        ));
+    }
+
+    let size = TokenMetadata {
+        mint: *mint,
+        name: metadata.name.clone(),
+        symbol: metadata.symbol.clone(),
+        uri: metadata.uri.clone(),
+        ..Default::default()
+    }
+    .tlv_size_of()
+    .map_err(ApiError::instruction_build)?;
+
+    let instruction = spl_token_metadata_interface::instruction::initialize(
+        &spl_token_2022::id(),
+        mint,
+        update_authority,
+        mint,
+        mint_authority,
+        metadata.name.clone(),
+        metadata.symbol.clone(),
+        metadata.uri.clone(),
+    );
+
+    Ok((instruction, size))
+}
+
+/// Returns the batch together with the fee the mint will withhold.
+pub fn build_transfer_with_fee(payload: &TransferWithFeeRequest) -> Result<(InstructionBatch, u64), ApiError> {
+    let program_id = spl_token_2022::id();
+
+    let destination = validate_pubkey(&payload.destination)
+        .map_err(|e| ApiError::invalid_pubkey("destination", e))?;
+
+    let mint = validate_pubkey(&payload.mint)
+        .map_err(|e| ApiError::invalid_pubkey("mint", e))?;
+
+    let owner = validate_pubkey(&payload.owner)
+        .map_err(|e| ApiError::invalid_pubkey("owner", e))?;
+
+    validate_basis_points("basisPoints", payload.basis_points)?;
+
+    // Same calculation the program runs; the instruction fails unless the
+    // fee we pass matches it exactly
+    let fee = TransferFee {
+        epoch: 0.into(),
+        maximum_fee: payload.maximum_fee.into(),
+        transfer_fee_basis_points: payload.basis_points.into(),
+    }
+    .calculate_fee(payload.amount)
+    .ok_or_else(|| ApiError::invalid_field("amount", "fee calculation overflowed"))?;
+
+    let signers = parse_multisig_signers(&payload.multisig_signers)?;
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+    let payer = default_fee_payer(owner, &signers);
+
+    let source_ata = get_associated_token_address_with_program_id(&owner, &mint, &program_id);
+    let destination_ata = get_associated_token_address_with_program_id(&destination, &mint, &program_id);
+
+    let mut instructions = Vec::with_capacity(2);
+    if payload.create_destination_account {
+        instructions.push(create_associated_token_account_idempotent(
+            &payer,
+            &destination,
+            &mint,
+            &program_id,
+        ));
+    }
+
+    instructions.push(transfer_fee::instruction::transfer_checked_with_fee(
+        &program_id,
+        &source_ata,
+        &mint,
+        &destination_ata,
+        &owner,
+        &signer_refs,
+        payload.amount,
+        payload.decimals,
+        fee,
+    ).map_err(ApiError::instruction_build)?);
+
+    Ok((
+        InstructionBatch {
+            instructions,
+            default_fee_payer: payer,
+        },
+        fee,
+    ))
+}
+
+pub fn build_withdraw_withheld(payload: &WithdrawWithheldRequest) -> Result<InstructionBatch, ApiError> {
+    let mint = validate_pubkey(&payload.mint)
+        .map_err(|e| ApiError::invalid_pubkey("mint", e))?;
+
+    let destination = validate_pubkey(&payload.destination)
+        .map_err(|e| ApiError::invalid_pubkey("destination", e))?;
+
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+
+    let sources = parse_sources(&payload.sources)?;
+    let source_refs: Vec<&Pubkey> = sources.iter().collect();
+
+    let signers = parse_multisig_signers(&payload.multisig_signers)?;
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+
+    let instruction = transfer_fee::instruction::withdraw_withheld_tokens_from_accounts(
+        &spl_token_2022::id(),
+        &mint,
+        &destination,
+        &authority,
+        &signer_refs,
+        &source_refs,
+    ).map_err(ApiError::instruction_build)?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction],
+        default_fee_payer: default_fee_payer(authority, &signers),
+    })
+}
+
+pub fn build_harvest_withheld(payload: &HarvestWithheldRequest) -> Result<InstructionBatch, ApiError> {
+    let mint = validate_pubkey(&payload.mint)
+        .map_err(|e| ApiError::invalid_pubkey("mint", e))?;
+
+    let payer = validate_pubkey(&payload.payer)
+        .map_err(|e| ApiError::invalid_pubkey("payer", e))?;
+
+    let sources = parse_sources(&payload.sources)?;
+    let source_refs: Vec<&Pubkey> = sources.iter().collect();
+
+    let instruction = transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+        &spl_token_2022::id(),
+        &mint,
+        &source_refs,
+    ).map_err(ApiError::instruction_build)?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction],
+        default_fee_payer: payer,
+    })
+}
+
+pub fn build_set_transfer_fee(payload: &SetTransferFeeRequest) -> Result<InstructionBatch, ApiError> {
+    let mint = validate_pubkey(&payload.mint)
+        .map_err(|e| ApiError::invalid_pubkey("mint", e))?;
+
+    let authority = validate_pubkey(&payload.authority)
+        .map_err(|e| ApiError::invalid_pubkey("authority", e))?;
+
+    validate_basis_points("basisPoints", payload.basis_points)?;
+
+    let signers = parse_multisig_signers(&payload.multisig_signers)?;
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+
+    let instruction = transfer_fee::instruction::set_transfer_fee(
+        &spl_token_2022::id(),
+        &mint,
+        &authority,
+        &signer_refs,
+        payload.basis_points,
+        payload.maximum_fee,
+    ).map_err(ApiError::instruction_build)?;
+
+    Ok(InstructionBatch {
+        instructions: vec![instruction],
+        default_fee_payer: default_fee_payer(authority, &signers),
+    })
+}
+
+pub async fn transfer_with_fee(
+    ApiJson(payload): ApiJson<TransferWithFeeRequest>,
+) -> Result<Json<TransferWithFeeResponse>, ApiError> {
+    let (batch, fee) = build_transfer_with_fee(&payload)?;
+    let transaction = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(TransferWithFeeResponse {
+        transaction,
+        fee,
+        net_amount: payload.amount - fee,
+    }))
+}
+
+pub async fn withdraw_withheld(
+    ApiJson(payload): ApiJson<WithdrawWithheldRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_withdraw_withheld(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn harvest_withheld(
+    ApiJson(payload): ApiJson<HarvestWithheldRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_harvest_withheld(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn set_transfer_fee(
+    ApiJson(payload): ApiJson<SetTransferFeeRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_set_transfer_fee(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer(amount: u64, basis_points: u16, maximum_fee: u64) -> TransferWithFeeRequest {
+        TransferWithFeeRequest {
+            destination: Pubkey::new_unique().to_string(),
+            mint: Pubkey::new_unique().to_string(),
+            owner: Pubkey::new_unique().to_string(),
+            amount,
+            decimals: 6,
+            basis_points,
+            maximum_fee,
+            create_destination_account: false,
+            multisig_signers: Vec::new(),
+            options: Default::default(),
+        }
+    }
+
+    fn fee_for(amount: u64, basis_points: u16, maximum_fee: u64) -> u64 {
+        let (batch, fee) = build_transfer_with_fee(&transfer(amount, basis_points, maximum_fee)).unwrap();
+
+        // The fee in the instruction is the one the program checks against
+        let data = &batch.instructions[0].data;
+        assert_eq!(data[data.len() - 8..], fee.to_le_bytes());
+        fee
+    }
+
+    #[test]
+    fn rounds_transfer_fees_up() {
+        assert_eq!(fee_for(1_000, 50, u64::MAX), 5);
+        assert_eq!(fee_for(1_001, 50, u64::MAX), 6);
+        assert_eq!(fee_for(1, 1, u64::MAX), 1);
+        assert_eq!(fee_for(0, 50, u64::MAX), 0);
+        assert_eq!(fee_for(1_000, 0, u64::MAX), 0);
+        assert_eq!(fee_for(u64::MAX, 10_000, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn clamps_transfer_fees_to_the_maximum() {
+        assert_eq!(fee_for(1_000_000, 100, 500), 500);
+        assert_eq!(fee_for(1_000_000, 100, 10_000), 10_000);
+        assert_eq!(fee_for(1_000_000, 100, 0), 0);
+    }
+
+    #[test]
+    fn rejects_fee_rates_over_one_hundred_percent() {
+        let error = build_transfer_with_fee(&transfer(1_000, 10_001, u64::MAX)).err().expect("rate accepted");
+        assert_eq!(error.field(), Some("basisPoints"));
+    }
+}
@@ -0,0 +1,499 @@
+use axum::response::Json;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_sdk::{message::VersionedMessage, pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
+use crate::handlers::{
+    build_create_token, build_mint_token, build_create_multisig, build_burn_token, build_approve_token, build_revoke_token,
+    build_freeze_token, build_thaw_token, build_close_token_account, build_sync_native,
+    build_set_authority, build_transfer_with_fee, build_withdraw_withheld, build_harvest_withheld,
+    build_set_transfer_fee, build_nft_mint,
+    build_send_sol, build_send_token,
+    build_create_account, build_create_account_with_seed, build_assign, build_assign_with_seed,
+    build_allocate, build_allocate_with_seed, build_transfer_with_seed, build_custom_instruction,
+    build_create_lookup_table, build_extend_lookup_table, build_freeze_lookup_table,
+    build_deactivate_lookup_table, build_close_lookup_table,
+    build_create_nonce_account, build_advance_nonce, build_withdraw_nonce, build_authorize_nonce,
+    build_create_stake_account, build_create_stake_account_with_seed, build_delegate_stake,
+    build_deactivate_stake, build_withdraw_stake, build_split_stake, build_merge_stake, build_authorize_stake,
+};
+use crate::models::{
+    ApiError, BuildTransactionRequest, InstructionSpec, TransactionResponse, DecodeTransactionRequest,
+    DecodedTransactionResponse, AccountMeta, AddressTableLookupResponse, MessageHeaderResponse,
+    SignTransactionRequest, SignedTransactionResponse, MergeSignaturesRequest, VerifyTransactionRequest,
+    TransactionVerificationResponse, SignatureVerification, SignatureStatus,
+};
+use crate::utils::{
+    ApiJson, InstructionBatch, validate_pubkey, instruction_to_transaction, deserialize_transaction,
+    decode_instruction, signature_report, signed_transaction_response, decode_base58_secret,
+    keypair_from_secret, sign_bytes, decode_base64_signature, verify_transaction_signature,
+};
+
+/// Steps only contribute instructions, so anything that configures a
+/// transaction of its own is rejected rather than silently dropped.
+pub fn build_instruction_spec(spec: &InstructionSpec) -> Result<InstructionBatch, ApiError> {
+    if let Some(option) = spec.options().first_set() {
+        return Err(ApiError::invalid_field(
+            option,
+            "transaction options belong on the top-level request, not on individual instructions",
+        ));
+    }
+
+    match spec {
+        InstructionSpec::CreateToken(payload) => build_create_token(payload).map(|(batch, _)| batch),
+        InstructionSpec::MintToken(payload) => build_mint_token(payload),
+        InstructionSpec::CreateMultisig(payload) => build_create_multisig(payload),
+        InstructionSpec::BurnToken(payload) => build_burn_token(payload),
+        InstructionSpec::ApproveToken(payload) => build_approve_token(payload),
+        InstructionSpec::RevokeToken(payload) => build_revoke_token(payload),
+        InstructionSpec::FreezeToken(payload) => build_freeze_token(payload),
+        InstructionSpec::ThawToken(payload) => build_thaw_token(payload),
+        InstructionSpec::CloseTokenAccount(payload) => build_close_token_account(payload),
+        InstructionSpec::SyncNative(payload) => build_sync_native(payload),
+        InstructionSpec::SetAuthority(payload) => build_set_authority(payload),
+        InstructionSpec::TransferWithFee(payload) => build_transfer_with_fee(payload).map(|(batch, _)| batch),
+        InstructionSpec::WithdrawWithheld(payload) => build_withdraw_withheld(payload),
+        InstructionSpec::HarvestWithheld(payload) => build_harvest_withheld(payload),
+        InstructionSpec::SetTransferFee(payload) => build_set_transfer_fee(payload),
+        InstructionSpec::NftMint(payload) => build_nft_mint(payload).map(|(batch, _)| batch),
+        InstructionSpec::SendSol(payload) => build_send_sol(payload),
+        InstructionSpec::SendToken(payload) => build_send_token(payload),
+        InstructionSpec::CreateAccount(payload) => build_create_account(payload),
+        InstructionSpec::CreateAccountWithSeed(payload) => build_create_account_with_seed(payload).map(|(batch, _)| batch),
+        InstructionSpec::Assign(payload) => build_assign(payload),
+        InstructionSpec::AssignWithSeed(payload) => build_assign_with_seed(payload).map(|(batch, _)| batch),
+        InstructionSpec::Allocate(payload) => build_allocate(payload),
+        InstructionSpec::AllocateWithSeed(payload) => build_allocate_with_seed(payload).map(|(batch, _)| batch),
+        InstructionSpec::TransferWithSeed(payload) => build_transfer_with_seed(payload).map(|(batch, _)| batch),
+        InstructionSpec::CustomInstruction(payload) if payload.include_transaction => Err(ApiError::invalid_field(
+            "includeTransaction",
+            "each instruction is already part of the composed transaction",
+        )),
+        // /transaction/build always has an explicit fee payer, so the default is never used
+        InstructionSpec::CustomInstruction(payload) => build_custom_instruction(payload).map(|instruction| InstructionBatch {
+            default_fee_payer: instruction.program_id,
+            instructions: vec![instruction],
+        }),
+        InstructionSpec::CreateLookupTable(payload) => build_create_lookup_table(payload).map(|(batch, _)| batch),
+        InstructionSpec::ExtendLookupTable(payload) => build_extend_lookup_table(payload),
+        InstructionSpec::FreezeLookupTable(payload) => build_freeze_lookup_table(payload),
+        InstructionSpec::DeactivateLookupTable(payload) => build_deactivate_lookup_table(payload),
+        InstructionSpec::CloseLookupTable(payload) => build_close_lookup_table(payload),
+        InstructionSpec::CreateNonceAccount(payload) => build_create_nonce_account(payload),
+        InstructionSpec::AdvanceNonce(payload) => build_advance_nonce(payload),
+        InstructionSpec::WithdrawNonce(payload) => build_withdraw_nonce(payload),
+        InstructionSpec::AuthorizeNonce(payload) => build_authorize_nonce(payload),
+        InstructionSpec::CreateStakeAccount(payload) => build_create_stake_account(payload),
+        InstructionSpec::CreateStakeAccountWithSeed(payload) => build_create_stake_account_with_seed(payload).map(|(batch, _)| batch),
+        InstructionSpec::DelegateStake(payload) => build_delegate_stake(payload),
+        InstructionSpec::DeactivateStake(payload) => build_deactivate_stake(payload),
+        InstructionSpec::WithdrawStake(payload) => build_withdraw_stake(payload),
+        InstructionSpec::SplitStake(payload) => build_split_stake(payload),
+        InstructionSpec::MergeStake(payload) => build_merge_stake(payload),
+        InstructionSpec::AuthorizeStake(payload) => build_authorize_stake(payload),
+    }
+}
+
+pub async fn build_transaction(
+    ApiJson(payload): ApiJson<BuildTransactionRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let fee_payer = payload.options.fee_payer.as_deref()
+        .ok_or_else(|| ApiError::invalid_field("feePayer", "fee payer is required"))?;
+    let fee_payer = validate_pubkey(fee_payer)
+        .map_err(|e| ApiError::invalid_pubkey("feePayer", e))?;
+
+    if payload.instructions.is_empty() {
+        return Err(ApiError::invalid_field("instructions", "at least one instruction is required"));
+    }
+
+    let mut instructions = Vec::new();
+    for (index, spec) in payload.instructions.iter().enumerate() {
+        let prefix = format!("instructions[{}]", index);
+        let batch = build_instruction_spec(spec).map_err(|e| e.nested(&prefix))?;
+        instructions.extend(batch.instructions);
+    }
+
+    let response_data = instruction_to_transaction(&instructions, fee_payer, &payload.options)?;
+
+    Ok(Json(response_data))
+}
+
+pub async fn decode_transaction(
+    ApiJson(payload): ApiJson<DecodeTransactionRequest>,
+) -> Result<Json<DecodedTransactionResponse>, ApiError> {
+    let transaction = deserialize_transaction("transaction", &payload.transaction, payload.encoding)?;
+    let message = &transaction.message;
+    let static_keys = message.static_account_keys();
+    let header = message.header();
+
+    let account_keys = static_keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| AccountMeta {
+            pubkey: key.to_string(),
+            is_signer: message.is_signer(index),
+            is_writable: message.is_maybe_writable(index),
+        })
+        .collect();
+
+    // Keys loaded from lookup tables follow the static keys: every table's
+    // writable entries first, then every table's readonly entries. Their
+    // addresses are unknown offline, so they are shown as `table[index]`.
+    let lookups = message.address_table_lookups().unwrap_or_default();
+    let mut all_keys: Vec<String> = static_keys.iter().map(|key| key.to_string()).collect();
+    for lookup in lookups {
+        all_keys.extend(lookup.writable_indexes.iter().map(|i| format!("{}[{}]", lookup.account_key, i)));
+    }
+    for lookup in lookups {
+        all_keys.extend(lookup.readonly_indexes.iter().map(|i| format!("{}[{}]", lookup.account_key, i)));
+    }
+
+    let instructions = message
+        .instructions()
+        .iter()
+        .map(|instruction| {
+            // Sanitization guarantees program ids are static keys and every
+            // account index is in range
+            let program_id = static_keys[instruction.program_id_index as usize];
+            let accounts = instruction.accounts.iter().map(|&i| all_keys[i as usize].clone()).collect();
+            decode_instruction(&program_id, accounts, &instruction.data)
+        })
+        .collect();
+
+    let response_data = DecodedTransactionResponse {
+        version: match message {
+            VersionedMessage::Legacy(_) => "legacy".to_string(),
+            VersionedMessage::V0(_) => "0".to_string(),
+        },
+        header: MessageHeaderResponse {
+            num_required_signatures: header.num_required_signatures,
+            num_readonly_signed_accounts: header.num_readonly_signed_accounts,
+            num_readonly_unsigned_accounts: header.num_readonly_unsigned_accounts,
+        },
+        account_keys,
+        address_table_lookups: lookups
+            .iter()
+            .map(|lookup| AddressTableLookupResponse {
+                account_key: lookup.account_key.to_string(),
+                writable_indexes: lookup.writable_indexes.clone(),
+                readonly_indexes: lookup.readonly_indexes.clone(),
+            })
+            .collect(),
+        recent_blockhash: message.recent_blockhash().to_string(),
+        signatures: signature_report(&transaction),
+        instructions,
+    };
+
+    Ok(Json(response_data))
+}
+
+pub async fn sign_transaction(
+    ApiJson(payload): ApiJson<SignTransactionRequest>,
+) -> Result<Json<SignedTransactionResponse>, ApiError> {
+    let mut transaction = deserialize_transaction("transaction", &payload.transaction, payload.encoding)?;
+
+    if payload.secrets.is_empty() {
+        return Err(ApiError::invalid_field("secrets", "at least one secret is required"));
+    }
+
+    let required_signers = &transaction.message.static_account_keys()
+        [..transaction.message.header().num_required_signatures as usize];
+
+    // Resolve every key before signing anything so a bad secret can't leave
+    // the caller with a half-applied result
+    let mut signers = Vec::with_capacity(payload.secrets.len());
+    for (index, secret) in payload.secrets.iter().enumerate() {
+        let field = format!("secrets[{}]", index);
+        let secret_bytes = decode_base58_secret(&field, secret)?;
+        let keypair = keypair_from_secret(&secret_bytes).map_err(|e| ApiError::InvalidSecret {
+            field: field.clone(),
+            reason: e.to_string(),
+        })?;
+
+        let pubkey = Pubkey::new_from_array(keypair.public.to_bytes());
+        let position = required_signers
+            .iter()
+            .position(|signer| *signer == pubkey)
+            .ok_or_else(|| ApiError::invalid_field(
+                &field,
+                format!("{} is not a required signer of this transaction", pubkey),
+            ))?;
+
+        signers.push((position, keypair));
+    }
+
+    let message_bytes = transaction.message.serialize();
+    for (position, keypair) in signers {
+        transaction.signatures[position] = Signature::from(sign_bytes(&message_bytes, &keypair));
+    }
+
+    Ok(Json(signed_transaction_response(&transaction)?))
+}
+
+pub async fn merge_signatures(
+    ApiJson(payload): ApiJson<MergeSignaturesRequest>,
+) -> Result<Json<SignedTransactionResponse>, ApiError> {
+    let (first, rest) = payload.transactions.split_first()
+        .ok_or_else(|| ApiError::invalid_field("transactions", "at least one transaction is required"))?;
+
+    let mut merged = deserialize_transaction("transactions[0]", first, payload.encoding)?;
+    let message_bytes = merged.message.serialize();
+    let num_required_signatures = merged.message.header().num_required_signatures as usize;
+    let signer_keys = merged.message.static_account_keys()[..num_required_signatures].to_vec();
+
+    // Every partial, the first one included, is verified before its
+    // signatures are copied, so start from empty slots
+    let mut partials = vec![(
+        "transactions[0]".to_string(),
+        std::mem::replace(&mut merged.signatures, vec![Signature::default(); num_required_signatures]),
+    )];
+    for (index, encoded) in rest.iter().enumerate() {
+        let field = format!("transactions[{}]", index + 1);
+        let transaction = deserialize_transaction(&field, encoded, payload.encoding)?;
+
+        if transaction.message.serialize() != message_bytes {
+            return Err(ApiError::invalid_field(field, "message differs from transactions[0]"));
+        }
+        partials.push((field, transaction.signatures));
+    }
+
+    for (field, signatures) in partials {
+        for (slot, signature) in signatures.into_iter().enumerate().take(num_required_signatures) {
+            if signature == Signature::default() {
+                continue;
+            }
+            let signature_field = format!("{}.signatures[{}]", field, slot);
+            apply_signature(&mut merged, &message_bytes, slot, &signer_keys[slot], signature, signature_field)?;
+        }
+    }
+
+    for (index, detached) in payload.signatures.iter().enumerate() {
+        let field = format!("signatures[{}]", index);
+        let pubkey = validate_pubkey(&detached.pubkey)
+            .map_err(|e| ApiError::invalid_pubkey(format!("{}.pubkey", field), e))?;
+        let slot = signer_keys
+            .iter()
+            .position(|signer| *signer == pubkey)
+            .ok_or_else(|| ApiError::invalid_field(
+                format!("{}.pubkey", field),
+                format!("{} is not a required signer of this transaction", pubkey),
+            ))?;
+
+        let signature_field = format!("{}.signature", field);
+        let signature = decode_base64_signature(&signature_field, &detached.signature)?;
+        let signature = Signature::from(signature.to_bytes());
+        apply_signature(&mut merged, &message_bytes, slot, &pubkey, signature, signature_field)?;
+    }
+
+    Ok(Json(signed_transaction_response(&merged)?))
+}
+
+pub async fn verify_transaction(
+    ApiJson(payload): ApiJson<VerifyTransactionRequest>,
+) -> Result<Json<TransactionVerificationResponse>, ApiError> {
+    let transaction = deserialize_transaction("transaction", &payload.transaction, payload.encoding)?;
+    let message_bytes = transaction.message.serialize();
+
+    let signatures: Vec<SignatureVerification> = transaction
+        .signatures
+        .iter()
+        .zip(transaction.message.static_account_keys())
+        .map(|(signature, key)| {
+            let status = if *signature == Signature::default() {
+                SignatureStatus::Placeholder
+            } else {
+                // Bytes that aren't even a well-formed signature count as invalid
+                match verify_transaction_signature(&message_bytes, signature, key) {
+                    Ok(true) => SignatureStatus::Valid,
+                    _ => SignatureStatus::Invalid,
+                }
+            };
+
+            SignatureVerification {
+                pubkey: key.to_string(),
+                signature: STANDARD.encode(signature),
+                status,
+            }
+        })
+        .collect();
+
+    Ok(Json(TransactionVerificationResponse {
+        valid: signatures.iter().all(|s| s.status == SignatureStatus::Valid),
+        signatures,
+    }))
+}
+
+/// Verifies `signature` for `signer` before placing it in its slot.
+fn apply_signature(
+    transaction: &mut VersionedTransaction,
+    message_bytes: &[u8],
+    slot: usize,
+    signer: &Pubkey,
+    signature: Signature,
+    field: String,
+) -> Result<(), ApiError> {
+    let valid = verify_transaction_signature(message_bytes, &signature, signer)
+        .map_err(|e| ApiError::invalid_signature(&field, e))?;
+    if !valid {
+        return Err(ApiError::invalid_signature(field, format!("does not verify for signer {}", signer)));
+    }
+
+    transaction.signatures[slot] = signature;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SignerSignature;
+    use solana_sdk::{
+        message::Message,
+        signature::{Keypair, Signer},
+        system_instruction,
+    };
+
+    /// A transfer from `from` whose fees are paid by `payer`, so it needs
+    /// both signatures.
+    fn two_signer_transaction(payer: &Keypair, from: &Keypair) -> VersionedTransaction {
+        let instruction = system_instruction::transfer(&from.pubkey(), &Pubkey::new_unique(), 1);
+        let message = Message::new(&[instruction], Some(&payer.pubkey()));
+        VersionedTransaction {
+            signatures: vec![Signature::default(); message.header.num_required_signatures as usize],
+            message: VersionedMessage::Legacy(message),
+        }
+    }
+
+    /// The transaction with only `signer`'s slot filled in.
+    fn signed_by(transaction: &VersionedTransaction, signer: &Keypair) -> VersionedTransaction {
+        let mut partial = transaction.clone();
+        let slot = partial.message.static_account_keys().iter().position(|key| *key == signer.pubkey()).unwrap();
+        partial.signatures[slot] = signer.sign_message(&partial.message.serialize());
+        partial
+    }
+
+    fn encode(transaction: &VersionedTransaction) -> String {
+        STANDARD.encode(bincode::serialize(transaction).unwrap())
+    }
+
+    async fn merge(transactions: Vec<String>, signatures: Vec<SignerSignature>) -> Result<SignedTransactionResponse, ApiError> {
+        merge_signatures(ApiJson(MergeSignaturesRequest { transactions, encoding: None, signatures }))
+            .await
+            .map(|Json(response)| response)
+    }
+
+    #[tokio::test]
+    async fn partial_then_complete_merge_fills_every_signer() {
+        let (payer, from) = (Keypair::new(), Keypair::new());
+        let unsigned = two_signer_transaction(&payer, &from);
+
+        let partial = merge(vec![encode(&signed_by(&unsigned, &payer))], Vec::new()).await.ok().unwrap();
+        assert!(!partial.complete);
+        assert_eq!(partial.missing_signers, [from.pubkey().to_string()]);
+
+        let complete = merge(vec![partial.transaction, encode(&signed_by(&unsigned, &from))], Vec::new()).await.ok().unwrap();
+        assert!(complete.complete);
+        assert!(complete.missing_signers.is_empty());
+        assert!(complete.signatures.iter().all(|signature| signature.present));
+
+        let Json(verification) = verify_transaction(ApiJson(VerifyTransactionRequest {
+            transaction: complete.transaction,
+            encoding: None,
+        }))
+        .await
+        .ok()
+        .unwrap();
+        assert!(verification.valid);
+    }
+
+    #[tokio::test]
+    async fn merges_detached_signatures() {
+        let (payer, from) = (Keypair::new(), Keypair::new());
+        let unsigned = two_signer_transaction(&payer, &from);
+        let signature = from.sign_message(&unsigned.message.serialize());
+
+        let merged = merge(
+            vec![encode(&signed_by(&unsigned, &payer))],
+            vec![SignerSignature { pubkey: from.pubkey().to_string(), signature: STANDARD.encode(signature) }],
+        )
+        .await
+        .ok()
+        .unwrap();
+        assert!(merged.complete);
+    }
+
+    #[tokio::test]
+    async fn rejects_signatures_from_the_wrong_key() {
+        let (payer, from) = (Keypair::new(), Keypair::new());
+        let unsigned = two_signer_transaction(&payer, &from);
+
+        // Signed by `from` but claimed for `payer`'s slot
+        let signature = from.sign_message(&unsigned.message.serialize());
+        let error = merge(
+            vec![encode(&unsigned)],
+            vec![SignerSignature { pubkey: payer.pubkey().to_string(), signature: STANDARD.encode(signature) }],
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(error.code(), "INVALID_SIGNATURE");
+        assert_eq!(error.field(), Some("signatures[0].signature"));
+
+        // A key that isn't a signer of the transaction at all
+        let outsider = Keypair::new();
+        let signature = outsider.sign_message(&unsigned.message.serialize());
+        let error = merge(
+            vec![encode(&unsigned)],
+            vec![SignerSignature { pubkey: outsider.pubkey().to_string(), signature: STANDARD.encode(signature) }],
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(error.field(), Some("signatures[0].pubkey"));
+
+        // A transaction signed by a keypair in the wrong slot
+        let mut misplaced = unsigned.clone();
+        misplaced.signatures[0] = from.sign_message(&unsigned.message.serialize());
+        let error = merge(vec![encode(&misplaced)], Vec::new()).await.err().unwrap();
+        assert_eq!(error.field(), Some("transactions[0].signatures[0]"));
+    }
+
+    #[tokio::test]
+    async fn rejects_tampered_signatures_and_messages() {
+        let (payer, from) = (Keypair::new(), Keypair::new());
+        let unsigned = two_signer_transaction(&payer, &from);
+
+        let mut tampered = signed_by(&unsigned, &from);
+        let mut bytes: [u8; 64] = tampered.signatures[1].into();
+        bytes[0] ^= 1;
+        tampered.signatures[1] = Signature::from(bytes);
+        let error = merge(vec![encode(&signed_by(&unsigned, &payer)), encode(&tampered)], Vec::new()).await.err().unwrap();
+        assert_eq!(error.code(), "INVALID_SIGNATURE");
+        assert_eq!(error.field(), Some("transactions[1].signatures[1]"));
+
+        // A signature over a different message doesn't carry over
+        let other = signed_by(&two_signer_transaction(&payer, &from), &from);
+        let error = merge(vec![encode(&signed_by(&unsigned, &payer)), encode(&other)], Vec::new()).await.err().unwrap();
+        assert_eq!(error.field(), Some("transactions[1]"));
+    }
+
+    #[tokio::test]
+    async fn rejects_transaction_fields_on_steps() {
+        let payer = Pubkey::new_unique().to_string();
+        let program = Pubkey::new_unique().to_string();
+        let cases = [
+            (serde_json::json!({ "type": "instruction/custom", "programId": program, "includeTransaction": true }), "instructions[1].includeTransaction"),
+            (serde_json::json!({ "type": "instruction/custom", "programId": program, "recentBlockhash": payer }), "instructions[1].recentBlockhash"),
+            (serde_json::json!({ "type": "send/sol", "from": payer, "to": program, "amount": 1, "feePayer": program }), "instructions[1].feePayer"),
+        ];
+
+        for (step, field) in cases {
+            let request = serde_json::json!({
+                "feePayer": payer,
+                "instructions": [{ "type": "instruction/custom", "programId": program }, step],
+            });
+            let payload: BuildTransactionRequest = serde_json::from_value(request).unwrap();
+            let Err(error) = build_transaction(ApiJson(payload)).await else {
+                panic!("{} accepted", field);
+            };
+            assert_eq!(error.field(), Some(field));
+        }
+    }
+}
@@ -1,89 +1,90 @@
-use axum::{extract::Json as ExtractJson, response::Json, http::StatusCode};
+use axum::response::Json;
 use solana_program::system_instruction;
-use spl_token::instruction;
-use crate::models::{TransactionResponse, SendSolRequest, SendTokenRequest};
-use crate::utils::{validate_pubkey, instruction_to_transaction};
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id, instruction::create_associated_token_account_idempotent,
+};
+use spl_token_2022::instruction;
+use solana_program::pubkey::Pubkey;
+use crate::handlers::token::{default_fee_payer, parse_multisig_signers, token_program_id};
+use crate::models::{ApiError, TransactionResponse, SendSolRequest, SendTokenRequest};
+use crate::utils::{ApiJson, InstructionBatch, validate_pubkey, instruction_to_transaction};
+
+pub fn build_send_sol(payload: &SendSolRequest) -> Result<InstructionBatch, ApiError> {
+    let from = validate_pubkey(&payload.from)
+        .map_err(|e| ApiError::invalid_pubkey("from", e))?;
+
+    let to = validate_pubkey(&payload.to)
+        .map_err(|e| ApiError::invalid_pubkey("to", e))?;
+
+    Ok(InstructionBatch {
+        instructions: vec![system_instruction::transfer(&from, &to, payload.amount)],
+        default_fee_payer: from,
+    })
+}
+
+pub fn build_send_token(payload: &SendTokenRequest) -> Result<InstructionBatch, ApiError> {
+    let program_id = token_program_id(payload.program);
+
+    let destination = validate_pubkey(&payload.destination)
+        .map_err(|e| ApiError::invalid_pubkey("destination", e))?;
+
+    let mint = validate_pubkey(&payload.mint)
+        .map_err(|e| ApiError::invalid_pubkey("mint", e))?;
+
+    let owner = validate_pubkey(&payload.owner)
+        .map_err(|e| ApiError::invalid_pubkey("owner", e))?;
+
+    let signers = parse_multisig_signers(&payload.multisig_signers)?;
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+    // A multisig owner can't sign, so it can't fund the destination account either
+    let payer = default_fee_payer(owner, &signers);
+
+    // `owner` and `destination` are wallets; the tokens move between their
+    // associated token accounts for `mint`.
+    let source_ata = get_associated_token_address_with_program_id(&owner, &mint, &program_id);
+    let destination_ata = get_associated_token_address_with_program_id(&destination, &mint, &program_id);
+
+    let mut instructions = Vec::with_capacity(2);
+    if payload.create_destination_account {
+        instructions.push(create_associated_token_account_idempotent(
+            &payer,
+            &destination,
+            &mint,
+            &program_id,
+        ));
+    }
+
+    instructions.push(instruction::transfer_checked(
+        &program_id,
+        &source_ata,
+        &mint,
+        &destination_ata,
+        &owner,
+        &signer_refs,
+        payload.amount,
+        payload.decimals,
+    ).map_err(ApiError::instruction_build)?);
+
+    Ok(InstructionBatch {
+        instructions,
+        default_fee_payer: payer,
+    })
+}
 
 pub async fn send_sol(
-    ExtractJson(payload): ExtractJson<SendSolRequest>,
-) -> Result<Json<TransactionResponse>, StatusCode> {
-    let from = match validate_pubkey(&payload.from) {
-        Ok(pubkey) => pubkey,
-        Err(e) => {
-            eprintln!("Invalid from pubkey: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    };
-
-    let to = match validate_pubkey(&payload.to) {
-        Ok(pubkey) => pubkey,
-        Err(e) => {
-            eprintln!("Invalid to pubkey: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    };
-
-    let instruction = system_instruction::transfer(&from, &to, payload.amount);
-    let response_data = match instruction_to_transaction(instruction, Some(from)) {
-        Ok(transaction) => transaction,
-        Err(e) => {
-            eprintln!("Failed to create transaction: {}", e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    ApiJson(payload): ApiJson<SendSolRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_send_sol(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
 
     Ok(Json(response_data))
 }
 
 pub async fn send_token(
-    ExtractJson(payload): ExtractJson<SendTokenRequest>,
-) -> Result<Json<TransactionResponse>, StatusCode> {
-    let destination = match validate_pubkey(&payload.destination) {
-        Ok(pubkey) => pubkey,
-        Err(e) => {
-            eprintln!("Invalid destination pubkey: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    };
-
-    let _mint = match validate_pubkey(&payload.mint) {
-        Ok(pubkey) => pubkey,
-        Err(e) => {
-            eprintln!("Invalid mint pubkey: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    };
-
-    let owner = match validate_pubkey(&payload.owner) {
-        Ok(pubkey) => pubkey,
-        Err(e) => {
-            eprintln!("Invalid owner pubkey: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    };
-
-    // For SPL token transfers, we need to derive the source token account
-    // In a real scenario, you'd need to get the actual source account
-    // For this example, we'll assume the owner is the source account
-    let instruction = instruction::transfer(
-        &spl_token::id(),
-        &owner,
-        &destination,
-        &owner,
-        &[],
-        payload.amount,
-    ).map_err(|e| {
-        eprintln!("Failed to create token transfer instruction: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
-
-    let response_data = match instruction_to_transaction(instruction, Some(owner)) {
-        Ok(transaction) => transaction,
-        Err(e) => {
-            eprintln!("Failed to create transaction: {}", e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    ApiJson(payload): ApiJson<SendTokenRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let batch = build_send_token(&payload)?;
+    let response_data = instruction_to_transaction(&batch.instructions, batch.default_fee_payer, &payload.options)?;
 
     Ok(Json(response_data))
 }
@@ -1,3 +1,5 @@
+#![recursion_limit = "256"]
+
 mod handlers;
 mod models;
 mod utils;
@@ -5,13 +7,14 @@ mod utils;
 use axum::{
     routing::{get, post},
     Router,
-    response::{Json, Response, IntoResponse},
-    http::{Method, HeaderValue, header, StatusCode},
-    extract::rejection::JsonRejection,
+    response::Json,
+    http::{Method, HeaderValue, header},
 };
 use tower_http::cors::{CorsLayer, Any};
 use tower_http::set_header::SetResponseHeaderLayer;
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
 use serde_json::json;
 
 async fn root() -> Json<serde_json::Value> {
@@ -27,6 +30,55 @@ async fn root() -> Json<serde_json::Value> {
             "POST /message/verify - Verify Ed25519 signature",
             "POST /send/sol - Create SOL transfer instruction",
             "POST /send/token - Create SPL token transfer instruction",
+            "POST /transaction/build - Compose several instructions into one transaction",
+            "POST /lookup-table/create - Create an address lookup table",
+            "POST /lookup-table/extend - Add addresses to a lookup table",
+            "POST /lookup-table/freeze - Make a lookup table immutable",
+            "POST /lookup-table/deactivate - Deactivate a lookup table",
+            "POST /lookup-table/close - Close a deactivated lookup table",
+            "POST /nonce/create - Create and initialize a durable nonce account",
+            "POST /nonce/advance - Advance a durable nonce",
+            "POST /nonce/withdraw - Withdraw lamports from a nonce account",
+            "POST /nonce/authorize - Change a nonce account's authority",
+            "POST /transaction/decode - Decode a serialized transaction into readable JSON",
+            "POST /transaction/sign - Sign a serialized transaction with one or more secrets",
+            "POST /transaction/merge-signatures - Combine partial signatures from several signers",
+            "POST /transaction/verify - Verify every signature of a serialized transaction",
+            "POST /token/burn - Burn tokens from an account",
+            "POST /token/approve - Approve a delegate",
+            "POST /token/revoke - Revoke a delegate",
+            "POST /token/freeze - Freeze a token account",
+            "POST /token/thaw - Thaw a frozen token account",
+            "POST /token/close - Close a token account",
+            "POST /token/sync-native - Sync a wrapped SOL account",
+            "POST /token/set-authority - Change or revoke a mint or token account authority",
+            "POST /token/create-multisig - Create an M-of-N SPL Token multisig account",
+            "POST /token/transfer-with-fee - Token-2022 transfer with the transfer fee computed server-side",
+            "POST /token/withdraw-withheld - Withdraw withheld transfer fees from token accounts",
+            "POST /token/harvest-withheld - Harvest withheld transfer fees to the mint",
+            "POST /token/set-transfer-fee - Change a mint's transfer fee",
+            "POST /nft/mint - Mint a 1-of-1 NFT with Metaplex metadata and master edition in one transaction",
+            "POST /stake/create - Create and initialize a stake account",
+            "POST /stake/create-with-seed - Create a stake account at an address derived from base and seed",
+            "POST /stake/delegate - Delegate a stake account to a validator",
+            "POST /stake/deactivate - Deactivate a stake account",
+            "POST /stake/withdraw - Withdraw lamports from a stake account",
+            "POST /stake/split - Split part of a stake account into a new one",
+            "POST /stake/merge - Merge one stake account into another",
+            "POST /stake/authorize - Change a stake account's staker or withdrawer",
+            "POST /system/create-account - Create an account owned by any program",
+            "POST /system/create-account-with-seed - Create an account at an address derived from base, seed and owner",
+            "POST /system/assign - Assign an account to a program",
+            "POST /system/assign-with-seed - Assign a seed-derived account to a program",
+            "POST /system/allocate - Allocate space in an account",
+            "POST /system/allocate-with-seed - Allocate space in a seed-derived account",
+            "POST /system/transfer-with-seed - Transfer SOL out of a seed-derived account",
+            "POST /instruction/custom - Build an instruction for any program from accounts and data",
+            "GET /idl - List registered IDL names",
+            "POST /idl/{name} - Register an Anchor IDL under a name",
+            "POST /idl/{name}/{instruction} - Build an instruction from a registered IDL",
+            "POST /idl/{name}/decode-account - Decode account data with a registered IDL",
+            "POST /idl/{name}/decode-events - Decode Anchor events from transaction logs",
             "GET /health - Health check endpoint"
         ],
         "documentation": "https://github.com/solana-labs/solana",
@@ -79,11 +131,17 @@ async fn api_docs() -> Json<serde_json::Value> {
                 }
             },
             "POST /token/create": {
-                "description": "Create SPL Token InitializeMint instruction",
+                "description": "Create SPL Token InitializeMint instruction; with createAccount the mint account is created too",
                 "request_body": {
                     "mintAuthority": "base58_encoded_pubkey",
                     "mint": "base58_encoded_pubkey",
-                    "decimals": 9
+                    "decimals": 9,
+                    "freezeAuthority": "optional base58_encoded_pubkey",
+                    "createAccount": "optional bool, default false",
+                    "payer": "optional base58_encoded_pubkey funding the mint and metadata accounts, defaults to mintAuthority",
+                    "program": "optional token | token-2022, default token",
+                    "extensions": "optional, token-2022 only: transferFee, closeAuthority, nonTransferable, interestBearing, defaultAccountState, permanentDelegate, metadataPointer",
+                    "metadata": "optional {name, symbol, uri, updateAuthority?}; Metaplex metadata for token, also sellerFeeBasisPoints, creators [{address, share}], isMutable; stored in the mint for token-2022"
                 },
                 "response_example": {
                     "success": true,
@@ -100,7 +158,9 @@ async fn api_docs() -> Json<serde_json::Value> {
                     "mint": "base58_encoded_pubkey",
                     "destination": "base58_encoded_pubkey",
                     "authority": "base58_encoded_pubkey",
-                    "amount": 1000000
+                    "amount": 1000000,
+                    "multisigSigners": "optional [base58_encoded_pubkey]",
+                    "program": "optional token | token-2022, default token"
                 }
             },
             "POST /message/sign": {
@@ -140,12 +200,689 @@ async fn api_docs() -> Json<serde_json::Value> {
                 }
             },
             "POST /send/token": {
-                "description": "Create SPL token transfer instruction",
+                "description": "Create SPL token TransferChecked instruction between the owner's and destination's associated token accounts",
+                "request_body": {
+                    "destination": "base58_encoded_wallet_pubkey",
+                    "mint": "base58_encoded_pubkey",
+                    "owner": "base58_encoded_wallet_pubkey",
+                    "amount": 1000000,
+                    "decimals": 6,
+                    "createDestinationAccount": "optional bool, prepends an idempotent ATA creation for the destination",
+                    "multisigSigners": "optional [base58_encoded_pubkey]",
+                    "program": "optional token | token-2022, default token"
+                }
+            },
+            "POST /transaction/build": {
+                "description": "Compose an ordered list of instructions into a single transaction",
+                "request_body": {
+                    "feePayer": "base58_encoded_pubkey",
+                    "instructions": [
+                        {
+                            "type": "token/* | nft/mint | send/sol | send/token | system/* | lookup-table/* | nonce/* | stake/* | instruction/custom",
+                            "...": "fields of the matching endpoint's request body"
+                        }
+                    ]
+                }
+            },
+            "POST /lookup-table/create": {
+                "description": "Create an address lookup table; the address is derived from authority + recentSlot",
+                "request_body": {
+                    "authority": "base58_encoded_pubkey",
+                    "payer": "optional base58_encoded_pubkey, defaults to authority",
+                    "recentSlot": 250000000
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully",
+                    "lookup_table_address": "base58_encoded_pubkey"
+                }
+            },
+            "POST /lookup-table/extend": {
+                "description": "Append addresses to a lookup table",
+                "request_body": {
+                    "lookupTable": "base58_encoded_pubkey",
+                    "authority": "base58_encoded_pubkey",
+                    "payer": "optional base58_encoded_pubkey, defaults to authority",
+                    "addresses": ["base58_encoded_pubkey"]
+                }
+            },
+            "POST /lookup-table/freeze": {
+                "description": "Permanently freeze a lookup table",
+                "request_body": {
+                    "lookupTable": "base58_encoded_pubkey",
+                    "authority": "base58_encoded_pubkey"
+                }
+            },
+            "POST /lookup-table/deactivate": {
+                "description": "Deactivate a lookup table so it can later be closed",
+                "request_body": {
+                    "lookupTable": "base58_encoded_pubkey",
+                    "authority": "base58_encoded_pubkey"
+                }
+            },
+            "POST /lookup-table/close": {
+                "description": "Close a deactivated lookup table and reclaim its rent",
+                "request_body": {
+                    "lookupTable": "base58_encoded_pubkey",
+                    "authority": "base58_encoded_pubkey",
+                    "recipient": "base58_encoded_pubkey"
+                }
+            },
+            "POST /nonce/create": {
+                "description": "Create and initialize a durable nonce account",
+                "request_body": {
+                    "from": "base58_encoded_funding_pubkey",
+                    "nonceAccount": "base58_encoded_new_account_pubkey",
+                    "authority": "base58_encoded_pubkey",
+                    "lamports": "optional u64, defaults to the rent-exempt minimum"
+                }
+            },
+            "POST /nonce/advance": {
+                "description": "Advance the nonce stored in a nonce account",
+                "request_body": {
+                    "nonceAccount": "base58_encoded_pubkey",
+                    "authority": "base58_encoded_pubkey"
+                }
+            },
+            "POST /nonce/withdraw": {
+                "description": "Withdraw lamports from a nonce account",
+                "request_body": {
+                    "nonceAccount": "base58_encoded_pubkey",
+                    "authority": "base58_encoded_pubkey",
+                    "to": "base58_encoded_pubkey",
+                    "lamports": 1000000
+                }
+            },
+            "POST /nonce/authorize": {
+                "description": "Assign a new authority to a nonce account",
+                "request_body": {
+                    "nonceAccount": "base58_encoded_pubkey",
+                    "authority": "base58_encoded_pubkey",
+                    "newAuthority": "base58_encoded_pubkey"
+                }
+            },
+            "POST /transaction/decode": {
+                "description": "Decode a base64 or base58 legacy/v0 transaction: header, accounts, signatures and instructions (System, SPL Token, ATA, Compute Budget and Memo are decoded; other programs return raw base64 data)",
                 "request_body": {
+                    "transaction": "base64_or_base58_encoded_transaction",
+                    "encoding": "optional base64 | base58, detected when omitted"
+                }
+            },
+            "POST /transaction/sign": {
+                "description": "Add signatures to a transaction using base58 secret keys. Partial signing is allowed; keys that are not required signers are rejected",
+                "request_body": {
+                    "transaction": "base64_or_base58_encoded_transaction",
+                    "encoding": "optional base64 | base58",
+                    "secrets": [
+                        "base58_encoded_secret_key"
+                    ]
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "signatures": [
+                        {
+                            "pubkey": "base58_encoded_pubkey",
+                            "signature": "base64_encoded_signature",
+                            "present": true
+                        }
+                    ],
+                    "missing_signers": [],
+                    "complete": true
+                }
+            },
+            "POST /transaction/merge-signatures": {
+                "description": "Merge signatures from copies of the same transaction and/or detached pubkey/signature pairs. Every signature is verified against its signer before it is added",
+                "request_body": {
+                    "transactions": [
+                        "base64_or_base58_encoded_transaction"
+                    ],
+                    "encoding": "optional base64 | base58",
+                    "signatures": [
+                        {
+                            "pubkey": "base58_encoded_pubkey",
+                            "signature": "base64_encoded_signature"
+                        }
+                    ]
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "signatures": [
+                        {
+                            "pubkey": "base58_encoded_pubkey",
+                            "signature": "base64_encoded_signature",
+                            "present": true
+                        }
+                    ],
+                    "missing_signers": [],
+                    "complete": true
+                }
+            },
+            "POST /transaction/verify": {
+                "description": "Check each signature against the serialized message and its signer. Unsigned slots are reported as placeholder rather than invalid",
+                "request_body": {
+                    "transaction": "base64_or_base58_encoded_transaction",
+                    "encoding": "optional base64 | base58"
+                },
+                "response_example": {
+                    "valid": false,
+                    "signatures": [
+                        {
+                            "pubkey": "base58_encoded_pubkey",
+                            "signature": "base64_encoded_signature",
+                            "status": "valid | invalid | placeholder"
+                        }
+                    ]
+                }
+            },
+            "POST /token/burn": {
+                "description": "Create a burn instruction; passing decimals uses burn_checked",
+                "request_body": {
+                    "account": "base58_encoded_token_account",
+                    "mint": "base58_encoded_mint",
+                    "authority": "base58_encoded_owner_or_delegate",
+                    "amount": "u64",
+                    "decimals": "optional u8",
+                    "multisigSigners": "optional [base58_encoded_pubkey]",
+                    "program": "optional token | token-2022, default token"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully"
+                }
+            },
+            "POST /token/approve": {
+                "description": "Let a delegate transfer or burn up to amount tokens; passing mint and decimals uses approve_checked",
+                "request_body": {
+                    "source": "base58_encoded_token_account",
+                    "delegate": "base58_encoded_pubkey",
+                    "owner": "base58_encoded_owner",
+                    "amount": "u64",
+                    "mint": "optional base58_encoded_mint",
+                    "decimals": "optional u8",
+                    "multisigSigners": "optional [base58_encoded_pubkey]",
+                    "program": "optional token | token-2022, default token"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully"
+                }
+            },
+            "POST /token/revoke": {
+                "description": "Remove the delegate of a token account",
+                "request_body": {
+                    "source": "base58_encoded_token_account",
+                    "owner": "base58_encoded_owner",
+                    "multisigSigners": "optional [base58_encoded_pubkey]",
+                    "program": "optional token | token-2022, default token"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully"
+                }
+            },
+            "POST /token/freeze": {
+                "description": "Freeze a token account using the mint's freeze authority",
+                "request_body": {
+                    "account": "base58_encoded_token_account",
+                    "mint": "base58_encoded_mint",
+                    "freezeAuthority": "base58_encoded_pubkey",
+                    "multisigSigners": "optional [base58_encoded_pubkey]",
+                    "program": "optional token | token-2022, default token"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully"
+                }
+            },
+            "POST /token/thaw": {
+                "description": "Thaw a frozen token account using the mint's freeze authority",
+                "request_body": {
+                    "account": "base58_encoded_token_account",
+                    "mint": "base58_encoded_mint",
+                    "freezeAuthority": "base58_encoded_pubkey",
+                    "multisigSigners": "optional [base58_encoded_pubkey]",
+                    "program": "optional token | token-2022, default token"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully"
+                }
+            },
+            "POST /token/close": {
+                "description": "Close an empty token account and send its rent to destination",
+                "request_body": {
+                    "account": "base58_encoded_token_account",
                     "destination": "base58_encoded_pubkey",
+                    "owner": "base58_encoded_owner",
+                    "multisigSigners": "optional [base58_encoded_pubkey]",
+                    "program": "optional token | token-2022, default token"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully"
+                }
+            },
+            "POST /token/sync-native": {
+                "description": "Update a native (wrapped SOL) token account's amount to match its lamports",
+                "request_body": {
+                    "account": "base58_encoded_token_account",
+                    "payer": "base58_encoded_fee_payer",
+                    "program": "optional token | token-2022, default token"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully"
+                }
+            },
+            "POST /token/set-authority": {
+                "description": "Rotate an authority, or remove it permanently by omitting newAuthority and setting confirmRevoke",
+                "request_body": {
+                    "account": "base58_encoded_mint_or_token_account",
+                    "authorityType": "mintTokens | freezeAccount | accountOwner | closeAccount; token-2022 also transferFeeConfig | withheldWithdraw | closeMint | interestRate | permanentDelegate | metadataPointer",
+                    "currentAuthority": "base58_encoded_pubkey",
+                    "newAuthority": "optional base58_encoded_pubkey",
+                    "confirmRevoke": "bool, required to remove an authority",
+                    "multisigSigners": "optional [base58_encoded_pubkey]",
+                    "program": "optional token | token-2022, default token"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully"
+                }
+            },
+            "POST /token/create-multisig": {
+                "description": "Create and initialize a multisig account (initialize_multisig2) that can hold mint, freeze or owner authority",
+                "request_body": {
+                    "multisig": "base58_encoded_new_account",
+                    "payer": "base58_encoded_pubkey",
+                    "signers": [
+                        "base58_encoded_pubkey"
+                    ],
+                    "m": 2,
+                    "program": "optional token | token-2022, default token"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully"
+                }
+            },
+            "POST /token/transfer-with-fee": {
+                "description": "Create transfer_checked_with_fee between the owner's and destination's Token-2022 associated token accounts; basisPoints and maximumFee must match the mint's current fee",
+                "request_body": {
+                    "destination": "base58_encoded_wallet_pubkey",
                     "mint": "base58_encoded_pubkey",
-                    "owner": "base58_encoded_pubkey",
-                    "amount": 1000000
+                    "owner": "base58_encoded_wallet_pubkey",
+                    "amount": 1000000,
+                    "decimals": 6,
+                    "basisPoints": 50,
+                    "maximumFee": 5000,
+                    "createDestinationAccount": "optional bool",
+                    "multisigSigners": "optional [base58_encoded_pubkey]"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully",
+                    "fee": 5000,
+                    "net_amount": 995000
+                }
+            },
+            "POST /token/withdraw-withheld": {
+                "description": "Move withheld Token-2022 transfer fees from token accounts to destination using the withdraw authority",
+                "request_body": {
+                    "mint": "base58_encoded_pubkey",
+                    "destination": "base58_encoded_token_account",
+                    "authority": "base58_encoded_pubkey",
+                    "sources": [
+                        "base58_encoded_token_account"
+                    ],
+                    "multisigSigners": "optional [base58_encoded_pubkey]"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully"
+                }
+            },
+            "POST /token/harvest-withheld": {
+                "description": "Permissionlessly move withheld Token-2022 transfer fees from token accounts into the mint",
+                "request_body": {
+                    "mint": "base58_encoded_pubkey",
+                    "sources": [
+                        "base58_encoded_token_account"
+                    ],
+                    "payer": "base58_encoded_fee_payer"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully"
+                }
+            },
+            "POST /token/set-transfer-fee": {
+                "description": "Set a new Token-2022 transfer fee; it takes effect two epochs later",
+                "request_body": {
+                    "mint": "base58_encoded_pubkey",
+                    "authority": "base58_encoded_transfer_fee_config_authority",
+                    "basisPoints": 50,
+                    "maximumFee": 5000,
+                    "multisigSigners": "optional [base58_encoded_pubkey]"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully"
+                }
+            },
+            "POST /nft/mint": {
+                "description": "Create a 0-decimal mint, the owner's associated token account, mint one token, and create the Metaplex metadata and master edition; optionally verify a collection",
+                "request_body": {
+                    "mint": "base58_encoded_pubkey",
+                    "owner": "base58_encoded_wallet_pubkey",
+                    "mintAuthority": "optional base58_encoded_pubkey, defaults to owner",
+                    "payer": "optional base58_encoded_pubkey, defaults to mintAuthority",
+                    "metadata": {
+                        "name": "My NFT",
+                        "symbol": "NFT",
+                        "uri": "https://example.com/nft.json",
+                        "sellerFeeBasisPoints": 500,
+                        "creators": "optional [{address, share}]",
+                        "updateAuthority": "optional base58_encoded_pubkey, defaults to mintAuthority",
+                        "isMutable": "optional bool, default true"
+                    },
+                    "maxSupply": "optional number of prints, default 0",
+                    "collection": "optional {mint, authority?}; with authority the collection is verified"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully",
+                    "token_account": "base58_encoded_pubkey",
+                    "metadata_address": "base58_encoded_pubkey",
+                    "master_edition_address": "base58_encoded_pubkey"
+                }
+            },
+            "POST /stake/create": {
+                "description": "Create a stake account and initialize its authorities and lockup",
+                "request_body": {
+                    "from": "base58_encoded_funding_pubkey",
+                    "stakeAccount": "base58_encoded_new_account_pubkey",
+                    "staker": "optional base58_encoded_pubkey, defaults to from",
+                    "withdrawer": "optional base58_encoded_pubkey, defaults to from",
+                    "lockup": "optional {unixTimestamp, epoch, custodian}",
+                    "lamports": "u64, at least the rent-exempt minimum"
+                }
+            },
+            "POST /stake/create-with-seed": {
+                "description": "Create a stake account at create_with_seed(base, seed, stake program) and initialize it",
+                "request_body": {
+                    "from": "base58_encoded_funding_pubkey",
+                    "base": "base58_encoded_pubkey",
+                    "seed": "string, at most 32 bytes",
+                    "staker": "optional base58_encoded_pubkey, defaults to from",
+                    "withdrawer": "optional base58_encoded_pubkey, defaults to from",
+                    "lockup": "optional {unixTimestamp, epoch, custodian}",
+                    "lamports": "u64, at least the rent-exempt minimum"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully",
+                    "address": "base58_encoded_derived_stake_account"
+                }
+            },
+            "POST /stake/delegate": {
+                "description": "Delegate stake to a vote account",
+                "request_body": {
+                    "stakeAccount": "base58_encoded_pubkey",
+                    "staker": "base58_encoded_pubkey",
+                    "voteAccount": "base58_encoded_pubkey"
+                }
+            },
+            "POST /stake/deactivate": {
+                "description": "Deactivate delegated stake; it cools down over the following epochs",
+                "request_body": {
+                    "stakeAccount": "base58_encoded_pubkey",
+                    "staker": "base58_encoded_pubkey"
+                }
+            },
+            "POST /stake/withdraw": {
+                "description": "Withdraw inactive lamports from a stake account",
+                "request_body": {
+                    "stakeAccount": "base58_encoded_pubkey",
+                    "withdrawer": "base58_encoded_pubkey",
+                    "to": "base58_encoded_pubkey",
+                    "lamports": 1000000,
+                    "custodian": "optional base58_encoded_pubkey, needed while a lockup is in force"
+                }
+            },
+            "POST /stake/split": {
+                "description": "Move lamports from a stake account into a new stake account with the same authorities",
+                "request_body": {
+                    "stakeAccount": "base58_encoded_pubkey",
+                    "staker": "base58_encoded_pubkey",
+                    "splitStakeAccount": "base58_encoded_new_account_pubkey",
+                    "lamports": "u64, at least the rent-exempt minimum"
+                }
+            },
+            "POST /stake/merge": {
+                "description": "Merge source into stakeAccount; the source account is closed",
+                "request_body": {
+                    "stakeAccount": "base58_encoded_pubkey",
+                    "source": "base58_encoded_pubkey",
+                    "staker": "base58_encoded_pubkey"
+                }
+            },
+            "POST /stake/authorize": {
+                "description": "Assign a new staker or withdrawer authority",
+                "request_body": {
+                    "stakeAccount": "base58_encoded_pubkey",
+                    "authority": "base58_encoded_pubkey",
+                    "newAuthority": "base58_encoded_pubkey",
+                    "authorityType": "staker | withdrawer",
+                    "custodian": "optional base58_encoded_pubkey"
+                }
+            },
+            "POST /system/create-account": {
+                "description": "Create a new account with the given space and owner program",
+                "request_body": {
+                    "from": "base58_encoded_funding_pubkey",
+                    "newAccount": "base58_encoded_new_account_pubkey",
+                    "space": 165,
+                    "owner": "base58_encoded_program_id",
+                    "lamports": "optional u64, defaults to the rent-exempt minimum for space"
+                }
+            },
+            "POST /system/create-account-with-seed": {
+                "description": "Create a new account at create_with_seed(base, seed, owner)",
+                "request_body": {
+                    "from": "base58_encoded_funding_pubkey",
+                    "base": "base58_encoded_pubkey",
+                    "seed": "string, at most 32 bytes",
+                    "space": 165,
+                    "owner": "base58_encoded_program_id",
+                    "lamports": "optional u64, defaults to the rent-exempt minimum for space"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully",
+                    "address": "base58_encoded_derived_pubkey"
+                }
+            },
+            "POST /system/assign": {
+                "description": "Change the owner program of a System-owned account",
+                "request_body": {
+                    "account": "base58_encoded_pubkey",
+                    "owner": "base58_encoded_program_id"
+                }
+            },
+            "POST /system/assign-with-seed": {
+                "description": "Assign the account at create_with_seed(base, seed, owner) to owner",
+                "request_body": {
+                    "base": "base58_encoded_pubkey",
+                    "seed": "string, at most 32 bytes",
+                    "owner": "base58_encoded_program_id"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully",
+                    "address": "base58_encoded_derived_pubkey"
+                }
+            },
+            "POST /system/allocate": {
+                "description": "Allocate data space in a System-owned account",
+                "request_body": {
+                    "account": "base58_encoded_pubkey",
+                    "space": 165
+                }
+            },
+            "POST /system/allocate-with-seed": {
+                "description": "Allocate space in the account at create_with_seed(base, seed, owner) and assign it to owner",
+                "request_body": {
+                    "base": "base58_encoded_pubkey",
+                    "seed": "string, at most 32 bytes",
+                    "space": 165,
+                    "owner": "base58_encoded_program_id"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully",
+                    "address": "base58_encoded_derived_pubkey"
+                }
+            },
+            "POST /system/transfer-with-seed": {
+                "description": "Transfer lamports from the account at create_with_seed(base, seed, fromOwner)",
+                "request_body": {
+                    "base": "base58_encoded_pubkey",
+                    "seed": "string, at most 32 bytes",
+                    "fromOwner": "optional base58_encoded_program_id, defaults to the System program",
+                    "to": "base58_encoded_pubkey",
+                    "lamports": 1000000
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully",
+                    "address": "base58_encoded_derived_pubkey"
+                }
+            },
+            "POST /instruction/custom": {
+                "description": "Build an instruction from a program id, ordered accounts and data given as hex, base58, base64 or typed Borsh fields; optionally wrap it in a transaction",
+                "request_body": {
+                    "programId": "base58_encoded_program_id",
+                    "accounts": [
+                        {
+                            "pubkey": "base58_encoded_pubkey",
+                            "isSigner": true,
+                            "isWritable": true
+                        }
+                    ],
+                    "data": "optional {encoding: hex | base58 | base64, value} or {encoding: borsh, fields: [{type: u8 | u16 | u32 | u64 | i64 | bool | pubkey | string | vec<type>, value}]}",
+                    "includeTransaction": "optional bool, default false; transaction options are accepted with it"
+                },
+                "response_example": {
+                    "program_id": "base58_encoded_program_id",
+                    "accounts": "array_of_account_metas",
+                    "instruction_data": "base64_encoded_data",
+                    "transaction": "optional TransactionResponse"
+                }
+            },
+            "GET /idl": {
+                "description": "List the names of registered IDLs",
+                "response_example": {
+                    "idls": ["idl_name"]
+                }
+            },
+            "POST /idl/{name}": {
+                "description": "Register an Anchor IDL (legacy or 0.30+ format) under a name of letters, digits, '_' or '-'. Re-registering a name replaces it; IDLs are written to IDL_DIR when it is set",
+                "request_body": "the IDL JSON as produced by anchor build",
+                "response_example": {
+                    "name": "idl_name",
+                    "program_id": "optional base58_encoded_program_id",
+                    "instructions": [
+                        "instruction_name"
+                    ],
+                    "accounts": [
+                        "account_name"
+                    ],
+                    "events": [
+                        "event_name"
+                    ],
+                    "persisted": false
+                }
+            },
+            "POST /idl/{name}/{instruction}": {
+                "description": "Encode an instruction from a registered IDL: discriminator plus Borsh-encoded args, accounts in IDL order with fixed addresses and PDAs resolved from their seeds",
+                "request_body": {
+                    "args": {
+                        "argName": "JSON value matching the IDL type; enums as \"Variant\" or {\"Variant\": fields}, options as null"
+                    },
+                    "accounts": {
+                        "accountName": "base58_encoded_pubkey, or an object for account groups"
+                    },
+                    "remainingAccounts": "optional [{pubkey, isSigner, isWritable}]",
+                    "programId": "optional base58_encoded_program_id, required if the IDL has no address"
+                },
+                "response_example": {
+                    "transaction": "base64_encoded_transaction",
+                    "message": "Transaction created successfully",
+                    "instruction_data": "base64_encoded_data",
+                    "accounts": [
+                        {
+                            "name": "account_name",
+                            "pubkey": "base58_encoded_pubkey",
+                            "is_signer": true,
+                            "is_writable": true
+                        }
+                    ]
+                }
+            },
+            "POST /idl/{name}/decode-account": {
+                "description": "Decode base64 account data with a registered IDL: the 8-byte discriminator picks the account type and the rest is Borsh-decoded into JSON",
+                "request_body": {
+                    "data": "base64_encoded_account_data",
+                    "account": "optional account type name; the data must then match its discriminator"
+                },
+                "response_example": {
+                    "account": "account_type_name",
+                    "data": {
+                        "field": "decoded value; 64- and 128-bit integers as strings, pubkeys as base58, bytes as base64"
+                    }
+                }
+            },
+            "POST /idl/{name}/decode-events": {
+                "description": "Decode the Anchor events in a transaction's log messages: 'Program data:' lines emitted by the IDL's program are matched by event discriminator and Borsh-decoded",
+                "request_body": {
+                    "logs": [
+                        "transaction log message"
+                    ]
+                },
+                "response_example": {
+                    "events": [
+                        {
+                            "name": "event_name",
+                            "data": {
+                                "field": "decoded value"
+                            },
+                            "log_index": 3
+                        }
+                    ]
+                }
+            }
+        },
+        "transaction_options": {
+            "description": "Optional fields accepted by every endpoint that returns a transaction",
+            "fields": {
+                "recentBlockhash": "base58_encoded_blockhash, defaults to all zeros",
+                "feePayer": "base58_encoded_pubkey, defaults to the endpoint's primary signer",
+                "version": "legacy | v0, defaults to legacy",
+                "addressLookupTables": [
+                    {
+                        "address": "base58_encoded_lookup_table_pubkey",
+                        "addresses": ["base58_encoded_pubkey"]
+                    }
+                ],
+                "computeBudget": {
+                    "unitLimit": "optional u32, at most 1400000",
+                    "microLamportsPerUnit": "optional u64 priority fee price"
+                },
+                "nonce": {
+                    "account": "base58_encoded_nonce_account",
+                    "authority": "base58_encoded_nonce_authority",
+                    "value": "base58_encoded_stored_nonce, used as the blockhash"
                 }
             }
         },
@@ -156,7 +893,10 @@ async fn api_docs() -> Json<serde_json::Value> {
             },
             "error_response": {
                 "success": false,
-                "error": "descriptive_error_message"
+                "data": null,
+                "error": "descriptive_error_message",
+                "code": "MACHINE_READABLE_ERROR_CODE",
+                "field": "offending_request_field"
             }
         },
         "encoding": {
@@ -168,24 +908,6 @@ async fn api_docs() -> Json<serde_json::Value> {
     }))
 }
 
-// Custom error handler for JSON parsing errors
-async fn handle_json_rejection(rejection: JsonRejection) -> Response {
-    let error_message = match rejection {
-        JsonRejection::JsonDataError(_) => "Invalid JSON format",
-        JsonRejection::JsonSyntaxError(_) => "JSON syntax error",
-        JsonRejection::MissingJsonContentType(_) => "Missing Content-Type: application/json header",
-        _ => "JSON parsing error",
-    };
-
-    let json_response = json!({
-        "success": false,
-        "data": null,
-        "error": error_message
-    });
-
-    (StatusCode::BAD_REQUEST, Json(json_response)).into_response()
-}
-
 #[tokio::main]
 async fn main() {
     let app = Router::new()
@@ -199,6 +921,58 @@ async fn main() {
         .route("/message/verify", post(handlers::verify_message))
         .route("/send/sol", post(handlers::send_sol))
         .route("/send/token", post(handlers::send_token))
+        .route("/transaction/build", post(handlers::build_transaction))
+        .route("/lookup-table/create", post(handlers::create_lookup_table))
+        .route("/lookup-table/extend", post(handlers::extend_lookup_table))
+        .route("/lookup-table/freeze", post(handlers::freeze_lookup_table))
+        .route("/lookup-table/deactivate", post(handlers::deactivate_lookup_table))
+        .route("/lookup-table/close", post(handlers::close_lookup_table))
+        .route("/nonce/create", post(handlers::create_nonce_account))
+        .route("/nonce/advance", post(handlers::advance_nonce))
+        .route("/nonce/withdraw", post(handlers::withdraw_nonce))
+        .route("/nonce/authorize", post(handlers::authorize_nonce))
+        .route("/transaction/decode", post(handlers::decode_transaction))
+        .route("/transaction/sign", post(handlers::sign_transaction))
+        .route("/transaction/merge-signatures", post(handlers::merge_signatures))
+        .route("/transaction/verify", post(handlers::verify_transaction))
+        .route("/token/burn", post(handlers::burn_token))
+        .route("/token/approve", post(handlers::approve_token))
+        .route("/token/revoke", post(handlers::revoke_token))
+        .route("/token/freeze", post(handlers::freeze_token))
+        .route("/token/thaw", post(handlers::thaw_token))
+        .route("/token/close", post(handlers::close_token_account))
+        .route("/token/sync-native", post(handlers::sync_native))
+        .route("/token/set-authority", post(handlers::set_authority))
+        .route("/token/create-multisig", post(handlers::create_multisig))
+        .route("/token/transfer-with-fee", post(handlers::transfer_with_fee))
+        .route("/token/withdraw-withheld", post(handlers::withdraw_withheld))
+        .route("/token/harvest-withheld", post(handlers::harvest_withheld))
+        .route("/token/set-transfer-fee", post(handlers::set_transfer_fee))
+        .route("/nft/mint", post(handlers::nft_mint))
+        .route("/stake/create", post(handlers::create_stake_account))
+        .route("/stake/create-with-seed", post(handlers::create_stake_account_with_seed))
+        .route("/stake/delegate", post(handlers::delegate_stake))
+        .route("/stake/deactivate", post(handlers::deactivate_stake))
+        .route("/stake/withdraw", post(handlers::withdraw_stake))
+        .route("/stake/split", post(handlers::split_stake))
+        .route("/stake/merge", post(handlers::merge_stake))
+        .route("/stake/authorize", post(handlers::authorize_stake))
+        .route("/system/create-account", post(handlers::create_account))
+        .route("/system/create-account-with-seed", post(handlers::create_account_with_seed))
+        .route("/system/assign", post(handlers::assign))
+        .route("/system/assign-with-seed", post(handlers::assign_with_seed))
+        .route("/system/allocate", post(handlers::allocate))
+        .route("/system/allocate-with-seed", post(handlers::allocate_with_seed))
+        .route("/system/transfer-with-seed", post(handlers::transfer_with_seed))
+        .route("/instruction/custom", post(handlers::custom_instruction))
+        .route("/idl", get(handlers::list_idls))
+        .route("/idl/:name", post(handlers::register_idl))
+        .route("/idl/:name/:instruction", post(handlers::idl_instruction))
+        .route("/idl/:name/decode-account", post(handlers::decode_idl_account))
+        .route("/idl/:name/decode-events", post(handlers::decode_idl_events))
+        .with_state(Arc::new(handlers::IdlRegistry::load(
+            std::env::var_os("IDL_DIR").map(PathBuf::from),
+        )))
         .layer(SetResponseHeaderLayer::if_not_present(
             header::CONTENT_TYPE,
             HeaderValue::from_static("application/json"),
@@ -229,6 +1003,55 @@ async fn main() {
     println!("  POST /message/verify");
     println!("  POST /send/sol");
     println!("  POST /send/token");
+    println!("  POST /transaction/build");
+    println!("  POST /lookup-table/create");
+    println!("  POST /lookup-table/extend");
+    println!("  POST /lookup-table/freeze");
+    println!("  POST /lookup-table/deactivate");
+    println!("  POST /lookup-table/close");
+    println!("  POST /nonce/create");
+    println!("  POST /nonce/advance");
+    println!("  POST /nonce/withdraw");
+    println!("  POST /nonce/authorize");
+    println!("  POST /transaction/decode");
+    println!("  POST /transaction/sign");
+    println!("  POST /transaction/merge-signatures");
+    println!("  POST /transaction/verify");
+    println!("  POST /token/burn");
+    println!("  POST /token/approve");
+    println!("  POST /token/revoke");
+    println!("  POST /token/freeze");
+    println!("  POST /token/thaw");
+    println!("  POST /token/close");
+    println!("  POST /token/sync-native");
+    println!("  POST /token/set-authority");
+    println!("  POST /token/create-multisig");
+    println!("  POST /token/transfer-with-fee");
+    println!("  POST /token/withdraw-withheld");
+    println!("  POST /token/harvest-withheld");
+    println!("  POST /token/set-transfer-fee");
+    println!("  POST /nft/mint");
+    println!("  POST /stake/create");
+    println!("  POST /stake/create-with-seed");
+    println!("  POST /stake/delegate");
+    println!("  POST /stake/deactivate");
+    println!("  POST /stake/withdraw");
+    println!("  POST /stake/split");
+    println!("  POST /stake/merge");
+    println!("  POST /stake/authorize");
+    println!("  POST /system/create-account");
+    println!("  POST /system/create-account-with-seed");
+    println!("  POST /system/assign");
+    println!("  POST /system/assign-with-seed");
+    println!("  POST /system/allocate");
+    println!("  POST /system/allocate-with-seed");
+    println!("  POST /system/transfer-with-seed");
+    println!("  POST /instruction/custom");
+    println!("  GET  /idl");
+    println!("  POST /idl/{{name}}");
+    println!("  POST /idl/{{name}}/{{instruction}}");
+    println!("  POST /idl/{{name}}/decode-account");
+    println!("  POST /idl/{{name}}/decode-events");
 
     let listener = tokio::net::TcpListener::bind(addr)
         .await
@@ -0,0 +1,146 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use std::fmt;
+use crate::models::ApiResponse;
+
+#[derive(Debug)]
+pub enum ApiError {
+    InvalidJson { path: Option<String>, reason: String },
+    MissingContentType,
+    InvalidPubkey { field: String, reason: String },
+    InvalidSecret { field: String, reason: String },
+    InvalidSecretLength { field: String, length: usize },
+    InvalidSignature { field: String, reason: String },
+    InvalidField { field: String, reason: String },
+    InstructionBuild(String),
+    TransactionBuild(String),
+    TransactionTooLarge { size: usize, limit: usize },
+    SigningFailed(String),
+    NotFound(String),
+    StorageFailed(String),
+}
+
+impl ApiError {
+    pub fn invalid_pubkey(field: impl Into<String>, err: impl fmt::Display) -> Self {
+        ApiError::InvalidPubkey { field: field.into(), reason: err.to_string() }
+    }
+
+    pub fn invalid_signature(field: impl Into<String>, err: impl fmt::Display) -> Self {
+        ApiError::InvalidSignature { field: field.into(), reason: err.to_string() }
+    }
+
+    pub fn invalid_field(field: impl Into<String>, err: impl fmt::Display) -> Self {
+        ApiError::InvalidField { field: field.into(), reason: err.to_string() }
+    }
+
+    pub fn instruction_build(err: impl fmt::Display) -> Self {
+        ApiError::InstructionBuild(err.to_string())
+    }
+
+    pub fn transaction_build(err: impl fmt::Display) -> Self {
+        ApiError::TransactionBuild(err.to_string())
+    }
+
+    /// Stable machine-readable code; clients match on this rather than the message.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::InvalidJson { .. } => "INVALID_JSON",
+            ApiError::MissingContentType => "MISSING_CONTENT_TYPE",
+            ApiError::InvalidPubkey { .. } => "INVALID_PUBKEY",
+            ApiError::InvalidSecret { .. } => "INVALID_SECRET",
+            ApiError::InvalidSecretLength { .. } => "INVALID_SECRET_LENGTH",
+            ApiError::InvalidSignature { .. } => "INVALID_SIGNATURE",
+            ApiError::InvalidField { .. } => "INVALID_FIELD",
+            ApiError::InstructionBuild(_) => "INSTRUCTION_BUILD_FAILED",
+            ApiError::TransactionBuild(_) => "TRANSACTION_BUILD_FAILED",
+            ApiError::TransactionTooLarge { .. } => "TRANSACTION_TOO_LARGE",
+            ApiError::SigningFailed(_) => "SIGNING_FAILED",
+            ApiError::NotFound(_) => "NOT_FOUND",
+            ApiError::StorageFailed(_) => "STORAGE_FAILED",
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::InstructionBuild(_)
+            | ApiError::TransactionBuild(_)
+            | ApiError::SigningFailed(_)
+            | ApiError::StorageFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    /// Name (or serde path) of the request field the error refers to, if any.
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            ApiError::InvalidJson { path, .. } => path.as_deref(),
+            ApiError::InvalidPubkey { field, .. }
+            | ApiError::InvalidSecret { field, .. }
+            | ApiError::InvalidSecretLength { field, .. }
+            | ApiError::InvalidSignature { field, .. }
+            | ApiError::InvalidField { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+
+    /// Re-roots the offending field under `prefix`, e.g. `mint` becomes
+    /// `instructions[2].mint` when the request was one step of a larger body.
+    pub fn nested(mut self, prefix: &str) -> Self {
+        match &mut self {
+            ApiError::InvalidPubkey { field, .. }
+            | ApiError::InvalidSecret { field, .. }
+            | ApiError::InvalidSecretLength { field, .. }
+            | ApiError::InvalidSignature { field, .. }
+            | ApiError::InvalidField { field, .. } => *field = format!("{}.{}", prefix, field),
+            ApiError::InvalidJson { path: Some(path), .. } => *path = format!("{}.{}", prefix, path),
+            _ => {}
+        }
+        self
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::InvalidJson { reason, .. } => write!(f, "Invalid JSON body: {}", reason),
+            ApiError::MissingContentType => write!(f, "Missing Content-Type: application/json header"),
+            ApiError::InvalidPubkey { field, reason } => write!(f, "{}: {}", field, reason),
+            ApiError::InvalidSecret { field, reason } => write!(f, "Invalid secret key in '{}': {}", field, reason),
+            ApiError::InvalidSecretLength { field, length } => {
+                write!(f, "Secret key in '{}' must be 32 bytes, got {}", field, length)
+            }
+            ApiError::InvalidSignature { field, reason } => write!(f, "Invalid signature in '{}': {}", field, reason),
+            ApiError::InvalidField { field, reason } => write!(f, "Invalid value for '{}': {}", field, reason),
+            ApiError::InstructionBuild(reason) => write!(f, "Failed to build instruction: {}", reason),
+            ApiError::TransactionBuild(reason) => write!(f, "Failed to build transaction: {}", reason),
+            ApiError::TransactionTooLarge { size, limit } => write!(
+                f,
+                "Transaction is {} bytes, over the {}-byte packet limit; split it up or use a v0 transaction with lookup tables",
+                size, limit
+            ),
+            ApiError::SigningFailed(reason) => write!(f, "Failed to sign: {}", reason),
+            ApiError::NotFound(what) => write!(f, "{} not found", what),
+            ApiError::StorageFailed(reason) => write!(f, "Failed to store: {}", reason),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        if status.is_server_error() {
+            eprintln!("{}", self);
+        }
+
+        let body = ApiResponse::<()>::error(
+            self.code(),
+            self.to_string(),
+            self.field().map(str::to_string),
+        );
+
+        (status, Json(body)).into_response()
+    }
+}
@@ -1,5 +1,7 @@
 pub mod response;
 pub mod request;
+pub mod error;
 
 pub use response::*;
 pub use request::*;
+pub use error::*;
@@ -1,11 +1,189 @@
 use serde::Deserialize;
 
+/// Transaction-level settings accepted by every endpoint that returns a
+/// serialized transaction.
+#[derive(Deserialize, Default)]
+pub struct TransactionOptions {
+    #[serde(rename = "recentBlockhash")]
+    pub recent_blockhash: Option<String>,
+    #[serde(rename = "feePayer")]
+    pub fee_payer: Option<String>,
+    pub version: Option<TransactionVersion>,
+    #[serde(rename = "addressLookupTables", default)]
+    pub address_lookup_tables: Vec<AddressLookupTableSpec>,
+    #[serde(rename = "computeBudget")]
+    pub compute_budget: Option<ComputeBudgetOptions>,
+    pub nonce: Option<NonceOptions>,
+}
+
+impl TransactionOptions {
+    pub fn is_empty(&self) -> bool {
+        self.first_set().is_none()
+    }
+
+    /// JSON name of the first option that was given, if any.
+    pub fn first_set(&self) -> Option<&'static str> {
+        if self.recent_blockhash.is_some() {
+            Some("recentBlockhash")
+        } else if self.fee_payer.is_some() {
+            Some("feePayer")
+        } else if self.version.is_some() {
+            Some("version")
+        } else if !self.address_lookup_tables.is_empty() {
+            Some("addressLookupTables")
+        } else if self.compute_budget.is_some() {
+            Some("computeBudget")
+        } else if self.nonce.is_some() {
+            Some("nonce")
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionVersion {
+    #[default]
+    #[serde(rename = "legacy")]
+    Legacy,
+    #[serde(rename = "v0")]
+    V0,
+}
+
+#[derive(Deserialize)]
+pub struct ComputeBudgetOptions {
+    #[serde(rename = "unitLimit")]
+    pub unit_limit: Option<u32>,
+    #[serde(rename = "microLamportsPerUnit")]
+    pub micro_lamports_per_unit: Option<u64>,
+}
+
+/// Durable nonce to use instead of a recent blockhash; `value` is the nonce
+/// currently stored in `account`.
+#[derive(Deserialize)]
+pub struct NonceOptions {
+    pub account: String,
+    pub authority: String,
+    pub value: String,
+}
+
+/// Contents of an on-chain address lookup table. There is no RPC access, so
+/// callers supply the table's address list themselves.
+#[derive(Deserialize)]
+pub struct AddressLookupTableSpec {
+    pub address: String,
+    pub addresses: Vec<String>,
+}
+
+/// Which token program the instruction targets.
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TokenProgram {
+    #[default]
+    #[serde(rename = "token")]
+    Token,
+    #[serde(rename = "token-2022")]
+    Token2022,
+}
+
+/// Token-2022 mint extensions, initialized before the mint itself.
+#[derive(Deserialize)]
+pub struct MintExtensions {
+    #[serde(rename = "transferFee")]
+    pub transfer_fee: Option<TransferFeeExtension>,
+    #[serde(rename = "closeAuthority")]
+    pub close_authority: Option<String>,
+    #[serde(rename = "nonTransferable", default)]
+    pub non_transferable: bool,
+    #[serde(rename = "interestBearing")]
+    pub interest_bearing: Option<InterestBearingExtension>,
+    #[serde(rename = "defaultAccountState")]
+    pub default_account_state: Option<DefaultAccountState>,
+    #[serde(rename = "permanentDelegate")]
+    pub permanent_delegate: Option<String>,
+    #[serde(rename = "metadataPointer")]
+    pub metadata_pointer: Option<MetadataPointerExtension>,
+}
+
+#[derive(Deserialize)]
+pub struct TransferFeeExtension {
+    #[serde(rename = "configAuthority")]
+    pub config_authority: Option<String>,
+    #[serde(rename = "withdrawAuthority")]
+    pub withdraw_authority: Option<String>,
+    #[serde(rename = "basisPoints")]
+    pub basis_points: u16,
+    #[serde(rename = "maximumFee")]
+    pub maximum_fee: u64,
+}
+
+#[derive(Deserialize)]
+pub struct InterestBearingExtension {
+    #[serde(rename = "rateAuthority")]
+    pub rate_authority: Option<String>,
+    /// Annual rate in basis points; may be negative.
+    pub rate: i16,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum DefaultAccountState {
+    #[serde(rename = "initialized")]
+    Initialized,
+    #[serde(rename = "frozen")]
+    Frozen,
+}
+
+#[derive(Deserialize)]
+pub struct MetadataPointerExtension {
+    pub authority: Option<String>,
+    #[serde(rename = "metadataAddress")]
+    pub metadata_address: Option<String>,
+}
+
+/// Name, symbol and URI for a new mint: a Metaplex metadata account for
+/// `token` mints, or Token-2022 metadata stored in the mint itself.
+#[derive(Deserialize, Clone)]
+pub struct TokenMetadataSpec {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    #[serde(rename = "updateAuthority")]
+    pub update_authority: Option<String>,
+    // Metaplex only
+    #[serde(rename = "sellerFeeBasisPoints", default)]
+    pub seller_fee_basis_points: u16,
+    #[serde(default)]
+    pub creators: Vec<CreatorSpec>,
+    #[serde(rename = "isMutable")]
+    pub is_mutable: Option<bool>,
+    /// Collection mint; recorded unverified.
+    pub collection: Option<String>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct CreatorSpec {
+    pub address: String,
+    pub share: u8,
+}
+
 #[derive(Deserialize)]
 pub struct CreateTokenRequest {
     #[serde(rename = "mintAuthority")]
     pub mint_authority: String,
     pub mint: String,
     pub decimals: u8,
+    #[serde(rename = "freezeAuthority")]
+    pub freeze_authority: Option<String>,
+    /// Also create the mint account (funded by `payer`) instead of assuming it exists.
+    #[serde(rename = "createAccount", default)]
+    pub create_account: bool,
+    pub payer: Option<String>,
+    /// Token-2022 only.
+    pub extensions: Option<MintExtensions>,
+    pub metadata: Option<TokenMetadataSpec>,
+    #[serde(default)]
+    pub program: TokenProgram,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
 }
 
 #[derive(Deserialize)]
@@ -14,6 +192,239 @@ pub struct MintTokenRequest {
     pub destination: String,
     pub authority: String,
     pub amount: u64,
+    #[serde(rename = "multisigSigners", default)]
+    pub multisig_signers: Vec<String>,
+    #[serde(default)]
+    pub program: TokenProgram,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// Collection a new NFT belongs to. With `authority` (the collection's
+/// update authority) the membership is verified in the same transaction.
+#[derive(Deserialize)]
+pub struct NftCollectionSpec {
+    pub mint: String,
+    pub authority: Option<String>,
+}
+
+/// Mints a 1-of-1 NFT to `owner`'s associated token account.
+#[derive(Deserialize)]
+pub struct NftMintRequest {
+    pub mint: String,
+    pub owner: String,
+    /// Defaults to `owner`.
+    #[serde(rename = "mintAuthority")]
+    pub mint_authority: Option<String>,
+    pub payer: Option<String>,
+    pub metadata: TokenMetadataSpec,
+    /// Prints allowed from the master edition; defaults to 0.
+    #[serde(rename = "maxSupply")]
+    pub max_supply: Option<u64>,
+    pub collection: Option<NftCollectionSpec>,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// Creates and initializes an M-of-N SPL Token multisig account.
+#[derive(Deserialize)]
+pub struct CreateMultisigRequest {
+    pub multisig: String,
+    pub payer: String,
+    pub signers: Vec<String>,
+    pub m: u8,
+    #[serde(default)]
+    pub program: TokenProgram,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// `decimals` switches to `burn_checked`.
+#[derive(Deserialize)]
+pub struct BurnTokenRequest {
+    pub account: String,
+    pub mint: String,
+    pub authority: String,
+    pub amount: u64,
+    pub decimals: Option<u8>,
+    #[serde(rename = "multisigSigners", default)]
+    pub multisig_signers: Vec<String>,
+    #[serde(default)]
+    pub program: TokenProgram,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// `mint` and `decimals` together switch to `approve_checked`.
+#[derive(Deserialize)]
+pub struct ApproveTokenRequest {
+    pub source: String,
+    pub delegate: String,
+    pub owner: String,
+    pub amount: u64,
+    pub mint: Option<String>,
+    pub decimals: Option<u8>,
+    #[serde(rename = "multisigSigners", default)]
+    pub multisig_signers: Vec<String>,
+    #[serde(default)]
+    pub program: TokenProgram,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct RevokeTokenRequest {
+    pub source: String,
+    pub owner: String,
+    #[serde(rename = "multisigSigners", default)]
+    pub multisig_signers: Vec<String>,
+    #[serde(default)]
+    pub program: TokenProgram,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// Used by both freeze and thaw.
+#[derive(Deserialize)]
+pub struct FreezeTokenRequest {
+    pub account: String,
+    pub mint: String,
+    #[serde(rename = "freezeAuthority")]
+    pub freeze_authority: String,
+    #[serde(rename = "multisigSigners", default)]
+    pub multisig_signers: Vec<String>,
+    #[serde(default)]
+    pub program: TokenProgram,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct CloseTokenAccountRequest {
+    pub account: String,
+    pub destination: String,
+    pub owner: String,
+    #[serde(rename = "multisigSigners", default)]
+    pub multisig_signers: Vec<String>,
+    #[serde(default)]
+    pub program: TokenProgram,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// `sync_native` needs no signer, so the caller names who pays for it.
+#[derive(Deserialize)]
+pub struct SyncNativeRequest {
+    pub account: String,
+    pub payer: String,
+    #[serde(default)]
+    pub program: TokenProgram,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum TokenAuthorityType {
+    #[serde(rename = "mintTokens")]
+    MintTokens,
+    #[serde(rename = "freezeAccount")]
+    FreezeAccount,
+    #[serde(rename = "accountOwner")]
+    AccountOwner,
+    #[serde(rename = "closeAccount")]
+    CloseAccount,
+    // Token-2022 mint extension authorities
+    #[serde(rename = "transferFeeConfig")]
+    TransferFeeConfig,
+    #[serde(rename = "withheldWithdraw")]
+    WithheldWithdraw,
+    #[serde(rename = "closeMint")]
+    CloseMint,
+    #[serde(rename = "interestRate")]
+    InterestRate,
+    #[serde(rename = "permanentDelegate")]
+    PermanentDelegate,
+    #[serde(rename = "metadataPointer")]
+    MetadataPointer,
+}
+
+/// Omitting `newAuthority` removes the authority for good, which must be
+/// acknowledged with `confirmRevoke`.
+#[derive(Deserialize)]
+pub struct SetAuthorityRequest {
+    pub account: String,
+    #[serde(rename = "authorityType")]
+    pub authority_type: TokenAuthorityType,
+    #[serde(rename = "currentAuthority")]
+    pub current_authority: String,
+    #[serde(rename = "newAuthority")]
+    pub new_authority: Option<String>,
+    #[serde(rename = "confirmRevoke", default)]
+    pub confirm_revoke: bool,
+    #[serde(rename = "multisigSigners", default)]
+    pub multisig_signers: Vec<String>,
+    #[serde(default)]
+    pub program: TokenProgram,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// Token-2022 `transfer_checked_with_fee` between the owner's and the
+/// destination's associated token accounts. `basisPoints` and `maximumFee`
+/// must match the mint's current transfer fee.
+#[derive(Deserialize)]
+pub struct TransferWithFeeRequest {
+    pub destination: String,
+    pub mint: String,
+    pub owner: String,
+    pub amount: u64,
+    pub decimals: u8,
+    #[serde(rename = "basisPoints")]
+    pub basis_points: u16,
+    #[serde(rename = "maximumFee")]
+    pub maximum_fee: u64,
+    #[serde(rename = "createDestinationAccount", default)]
+    pub create_destination_account: bool,
+    #[serde(rename = "multisigSigners", default)]
+    pub multisig_signers: Vec<String>,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct WithdrawWithheldRequest {
+    pub mint: String,
+    pub destination: String,
+    pub authority: String,
+    pub sources: Vec<String>,
+    #[serde(rename = "multisigSigners", default)]
+    pub multisig_signers: Vec<String>,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// Harvesting is permissionless, so the caller names who pays for it.
+#[derive(Deserialize)]
+pub struct HarvestWithheldRequest {
+    pub mint: String,
+    pub sources: Vec<String>,
+    pub payer: String,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct SetTransferFeeRequest {
+    pub mint: String,
+    pub authority: String,
+    #[serde(rename = "basisPoints")]
+    pub basis_points: u16,
+    #[serde(rename = "maximumFee")]
+    pub maximum_fee: u64,
+    #[serde(rename = "multisigSigners", default)]
+    pub multisig_signers: Vec<String>,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
 }
 
 #[derive(Deserialize)]
@@ -34,6 +445,85 @@ pub struct SendSolRequest {
     pub from: String,
     pub to: String,
     pub amount: u64,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// `lamports` defaults to the rent-exempt minimum for `space`.
+#[derive(Deserialize)]
+pub struct CreateAccountRequest {
+    pub from: String,
+    #[serde(rename = "newAccount")]
+    pub new_account: String,
+    pub space: u64,
+    pub owner: String,
+    pub lamports: Option<u64>,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// The new account's address is derived from `base`, `seed` and `owner`.
+#[derive(Deserialize)]
+pub struct CreateAccountWithSeedRequest {
+    pub from: String,
+    pub base: String,
+    pub seed: String,
+    pub space: u64,
+    pub owner: String,
+    pub lamports: Option<u64>,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct AssignRequest {
+    pub account: String,
+    pub owner: String,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct AssignWithSeedRequest {
+    pub base: String,
+    pub seed: String,
+    pub owner: String,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct AllocateRequest {
+    pub account: String,
+    pub space: u64,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// `owner` is part of the address derivation and is assigned along with
+/// the allocation.
+#[derive(Deserialize)]
+pub struct AllocateWithSeedRequest {
+    pub base: String,
+    pub seed: String,
+    pub space: u64,
+    pub owner: String,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// Transfers out of an account derived from `base`, `seed` and `fromOwner`
+/// (the System program unless given).
+#[derive(Deserialize)]
+pub struct TransferWithSeedRequest {
+    pub base: String,
+    pub seed: String,
+    #[serde(rename = "fromOwner")]
+    pub from_owner: Option<String>,
+    pub to: String,
+    pub lamports: u64,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
 }
 
 #[derive(Deserialize)]
@@ -42,4 +532,486 @@ pub struct SendTokenRequest {
     pub mint: String,
     pub owner: String,
     pub amount: u64,
+    pub decimals: u8,
+    #[serde(rename = "createDestinationAccount", default)]
+    pub create_destination_account: bool,
+    #[serde(rename = "multisigSigners", default)]
+    pub multisig_signers: Vec<String>,
+    #[serde(default)]
+    pub program: TokenProgram,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct CreateLookupTableRequest {
+    pub authority: String,
+    pub payer: Option<String>,
+    #[serde(rename = "recentSlot")]
+    pub recent_slot: u64,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct ExtendLookupTableRequest {
+    #[serde(rename = "lookupTable")]
+    pub lookup_table: String,
+    pub authority: String,
+    pub payer: Option<String>,
+    pub addresses: Vec<String>,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// Shared by the freeze and deactivate endpoints, which only need the table
+/// and its authority.
+#[derive(Deserialize)]
+pub struct LookupTableAuthorityRequest {
+    #[serde(rename = "lookupTable")]
+    pub lookup_table: String,
+    pub authority: String,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct CloseLookupTableRequest {
+    #[serde(rename = "lookupTable")]
+    pub lookup_table: String,
+    pub authority: String,
+    pub recipient: String,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct CreateNonceAccountRequest {
+    pub from: String,
+    #[serde(rename = "nonceAccount")]
+    pub nonce_account: String,
+    pub authority: String,
+    pub lamports: Option<u64>,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct AdvanceNonceRequest {
+    #[serde(rename = "nonceAccount")]
+    pub nonce_account: String,
+    pub authority: String,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct WithdrawNonceRequest {
+    #[serde(rename = "nonceAccount")]
+    pub nonce_account: String,
+    pub authority: String,
+    pub to: String,
+    pub lamports: u64,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct AuthorizeNonceRequest {
+    #[serde(rename = "nonceAccount")]
+    pub nonce_account: String,
+    pub authority: String,
+    #[serde(rename = "newAuthority")]
+    pub new_authority: String,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// Withdrawals are blocked until both `unixTimestamp` and `epoch` have
+/// passed, unless `custodian` signs. The defaults leave the stake unlocked.
+#[derive(Deserialize)]
+pub struct StakeLockupSpec {
+    #[serde(rename = "unixTimestamp", default)]
+    pub unix_timestamp: i64,
+    #[serde(default)]
+    pub epoch: u64,
+    pub custodian: Option<String>,
+}
+
+/// `staker` and `withdrawer` default to `from`.
+#[derive(Deserialize)]
+pub struct CreateStakeAccountRequest {
+    pub from: String,
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: String,
+    pub staker: Option<String>,
+    pub withdrawer: Option<String>,
+    pub lockup: Option<StakeLockupSpec>,
+    pub lamports: u64,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// The stake account address is derived from `base` and `seed`, so only
+/// `base` signs for it.
+#[derive(Deserialize)]
+pub struct CreateStakeAccountWithSeedRequest {
+    pub from: String,
+    pub base: String,
+    pub seed: String,
+    pub staker: Option<String>,
+    pub withdrawer: Option<String>,
+    pub lockup: Option<StakeLockupSpec>,
+    pub lamports: u64,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct DelegateStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: String,
+    pub staker: String,
+    #[serde(rename = "voteAccount")]
+    pub vote_account: String,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct DeactivateStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: String,
+    pub staker: String,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct WithdrawStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: String,
+    pub withdrawer: String,
+    pub to: String,
+    pub lamports: u64,
+    pub custodian: Option<String>,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct SplitStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: String,
+    pub staker: String,
+    #[serde(rename = "splitStakeAccount")]
+    pub split_stake_account: String,
+    pub lamports: u64,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// Merges `source` into `stakeAccount`; both need the same authorities.
+#[derive(Deserialize)]
+pub struct MergeStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: String,
+    pub source: String,
+    pub staker: String,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum StakeAuthorityType {
+    #[serde(rename = "staker")]
+    Staker,
+    #[serde(rename = "withdrawer")]
+    Withdrawer,
+}
+
+#[derive(Deserialize)]
+pub struct AuthorizeStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: String,
+    pub authority: String,
+    #[serde(rename = "newAuthority")]
+    pub new_authority: String,
+    #[serde(rename = "authorityType")]
+    pub authority_type: StakeAuthorityType,
+    pub custodian: Option<String>,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+#[derive(Deserialize)]
+pub struct CustomAccountMeta {
+    pub pubkey: String,
+    #[serde(rename = "isSigner", default)]
+    pub is_signer: bool,
+    #[serde(rename = "isWritable", default)]
+    pub is_writable: bool,
+}
+
+/// Instruction data, either already encoded or as Borsh fields to encode.
+#[derive(Deserialize)]
+#[serde(tag = "encoding")]
+pub enum InstructionDataSpec {
+    #[serde(rename = "hex")]
+    Hex { value: String },
+    #[serde(rename = "base58")]
+    Base58 { value: String },
+    #[serde(rename = "base64")]
+    Base64 { value: String },
+    #[serde(rename = "borsh")]
+    Borsh { fields: Vec<BorshFieldSpec> },
+}
+
+#[derive(Deserialize)]
+pub struct BorshFieldSpec {
+    #[serde(rename = "type")]
+    pub field_type: String,
+    pub value: serde_json::Value,
+}
+
+/// Any program's instruction, from raw accounts and data. Transaction
+/// options are only allowed together with `includeTransaction`.
+#[derive(Deserialize)]
+pub struct CustomInstructionRequest {
+    #[serde(rename = "programId")]
+    pub program_id: String,
+    #[serde(default)]
+    pub accounts: Vec<CustomAccountMeta>,
+    pub data: Option<InstructionDataSpec>,
+    #[serde(rename = "includeTransaction", default)]
+    pub include_transaction: bool,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// Arguments and accounts for an instruction of a registered Anchor IDL,
+/// keyed by name. Accounts the IDL can work out (fixed addresses, PDAs,
+/// well-known programs) may be left out.
+#[derive(Deserialize)]
+pub struct IdlInstructionRequest {
+    #[serde(default)]
+    pub args: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    pub accounts: serde_json::Map<String, serde_json::Value>,
+    #[serde(rename = "remainingAccounts", default)]
+    pub remaining_accounts: Vec<CustomAccountMeta>,
+    /// Overrides the IDL's program address.
+    #[serde(rename = "programId")]
+    pub program_id: Option<String>,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
+}
+
+/// Account data as fetched from RPC, discriminator included. `account`
+/// pins the expected account type instead of matching any in the IDL.
+#[derive(Deserialize)]
+pub struct DecodeAccountRequest {
+    pub data: String,
+    pub account: Option<String>,
+}
+
+/// Transaction log messages, in order, as returned in a transaction's meta.
+#[derive(Deserialize)]
+pub struct DecodeEventsRequest {
+    pub logs: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum TransactionEncoding {
+    #[serde(rename = "base64")]
+    Base64,
+    #[serde(rename = "base58")]
+    Base58,
+}
+
+/// `encoding` is detected from the input when omitted.
+#[derive(Deserialize)]
+pub struct DecodeTransactionRequest {
+    pub transaction: String,
+    pub encoding: Option<TransactionEncoding>,
+}
+
+#[derive(Deserialize)]
+pub struct SignTransactionRequest {
+    pub transaction: String,
+    pub encoding: Option<TransactionEncoding>,
+    pub secrets: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct VerifyTransactionRequest {
+    pub transaction: String,
+    pub encoding: Option<TransactionEncoding>,
+}
+
+#[derive(Deserialize)]
+pub struct SignerSignature {
+    pub pubkey: String,
+    pub signature: String,
+}
+
+/// Every entry in `transactions` must carry the same message; signatures are
+/// collected from all of them plus any detached `signatures`.
+#[derive(Deserialize)]
+pub struct MergeSignaturesRequest {
+    pub transactions: Vec<String>,
+    pub encoding: Option<TransactionEncoding>,
+    #[serde(default)]
+    pub signatures: Vec<SignerSignature>,
+}
+
+/// One step of a composed transaction; `type` names the endpoint whose
+/// request body the remaining fields follow.
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+pub enum InstructionSpec {
+    #[serde(rename = "token/create")]
+    CreateToken(Box<CreateTokenRequest>),
+    #[serde(rename = "token/mint")]
+    MintToken(MintTokenRequest),
+    #[serde(rename = "token/create-multisig")]
+    CreateMultisig(CreateMultisigRequest),
+    #[serde(rename = "token/burn")]
+    BurnToken(BurnTokenRequest),
+    #[serde(rename = "token/approve")]
+    ApproveToken(ApproveTokenRequest),
+    #[serde(rename = "token/revoke")]
+    RevokeToken(RevokeTokenRequest),
+    #[serde(rename = "token/freeze")]
+    FreezeToken(FreezeTokenRequest),
+    #[serde(rename = "token/thaw")]
+    ThawToken(FreezeTokenRequest),
+    #[serde(rename = "token/close")]
+    CloseTokenAccount(CloseTokenAccountRequest),
+    #[serde(rename = "token/sync-native")]
+    SyncNative(SyncNativeRequest),
+    #[serde(rename = "token/set-authority")]
+    SetAuthority(SetAuthorityRequest),
+    #[serde(rename = "token/transfer-with-fee")]
+    TransferWithFee(TransferWithFeeRequest),
+    #[serde(rename = "token/withdraw-withheld")]
+    WithdrawWithheld(WithdrawWithheldRequest),
+    #[serde(rename = "token/harvest-withheld")]
+    HarvestWithheld(HarvestWithheldRequest),
+    #[serde(rename = "token/set-transfer-fee")]
+    SetTransferFee(SetTransferFeeRequest),
+    #[serde(rename = "nft/mint")]
+    NftMint(Box<NftMintRequest>),
+    #[serde(rename = "send/sol")]
+    SendSol(SendSolRequest),
+    #[serde(rename = "send/token")]
+    SendToken(SendTokenRequest),
+    #[serde(rename = "system/create-account")]
+    CreateAccount(CreateAccountRequest),
+    #[serde(rename = "system/create-account-with-seed")]
+    CreateAccountWithSeed(CreateAccountWithSeedRequest),
+    #[serde(rename = "system/assign")]
+    Assign(AssignRequest),
+    #[serde(rename = "system/assign-with-seed")]
+    AssignWithSeed(AssignWithSeedRequest),
+    #[serde(rename = "system/allocate")]
+    Allocate(AllocateRequest),
+    #[serde(rename = "system/allocate-with-seed")]
+    AllocateWithSeed(AllocateWithSeedRequest),
+    #[serde(rename = "system/transfer-with-seed")]
+    TransferWithSeed(TransferWithSeedRequest),
+    #[serde(rename = "instruction/custom")]
+    CustomInstruction(CustomInstructionRequest),
+    #[serde(rename = "lookup-table/create")]
+    CreateLookupTable(CreateLookupTableRequest),
+    #[serde(rename = "lookup-table/extend")]
+    ExtendLookupTable(ExtendLookupTableRequest),
+    #[serde(rename = "lookup-table/freeze")]
+    FreezeLookupTable(LookupTableAuthorityRequest),
+    #[serde(rename = "lookup-table/deactivate")]
+    DeactivateLookupTable(LookupTableAuthorityRequest),
+    #[serde(rename = "lookup-table/close")]
+    CloseLookupTable(CloseLookupTableRequest),
+    #[serde(rename = "nonce/create")]
+    CreateNonceAccount(CreateNonceAccountRequest),
+    #[serde(rename = "nonce/advance")]
+    AdvanceNonce(AdvanceNonceRequest),
+    #[serde(rename = "nonce/withdraw")]
+    WithdrawNonce(WithdrawNonceRequest),
+    #[serde(rename = "nonce/authorize")]
+    AuthorizeNonce(AuthorizeNonceRequest),
+    #[serde(rename = "stake/create")]
+    CreateStakeAccount(CreateStakeAccountRequest),
+    #[serde(rename = "stake/create-with-seed")]
+    CreateStakeAccountWithSeed(CreateStakeAccountWithSeedRequest),
+    #[serde(rename = "stake/delegate")]
+    DelegateStake(DelegateStakeRequest),
+    #[serde(rename = "stake/deactivate")]
+    DeactivateStake(DeactivateStakeRequest),
+    #[serde(rename = "stake/withdraw")]
+    WithdrawStake(WithdrawStakeRequest),
+    #[serde(rename = "stake/split")]
+    SplitStake(SplitStakeRequest),
+    #[serde(rename = "stake/merge")]
+    MergeStake(MergeStakeRequest),
+    #[serde(rename = "stake/authorize")]
+    AuthorizeStake(AuthorizeStakeRequest),
+}
+
+impl InstructionSpec {
+    pub fn options(&self) -> &TransactionOptions {
+        match self {
+            InstructionSpec::CreateToken(payload) => &payload.options,
+            InstructionSpec::MintToken(payload) => &payload.options,
+            InstructionSpec::CreateMultisig(payload) => &payload.options,
+            InstructionSpec::BurnToken(payload) => &payload.options,
+            InstructionSpec::ApproveToken(payload) => &payload.options,
+            InstructionSpec::RevokeToken(payload) => &payload.options,
+            InstructionSpec::FreezeToken(payload) => &payload.options,
+            InstructionSpec::ThawToken(payload) => &payload.options,
+            InstructionSpec::CloseTokenAccount(payload) => &payload.options,
+            InstructionSpec::SyncNative(payload) => &payload.options,
+            InstructionSpec::SetAuthority(payload) => &payload.options,
+            InstructionSpec::TransferWithFee(payload) => &payload.options,
+            InstructionSpec::WithdrawWithheld(payload) => &payload.options,
+            InstructionSpec::HarvestWithheld(payload) => &payload.options,
+            InstructionSpec::SetTransferFee(payload) => &payload.options,
+            InstructionSpec::NftMint(payload) => &payload.options,
+            InstructionSpec::SendSol(payload) => &payload.options,
+            InstructionSpec::SendToken(payload) => &payload.options,
+            InstructionSpec::CreateAccount(payload) => &payload.options,
+            InstructionSpec::CreateAccountWithSeed(payload) => &payload.options,
+            InstructionSpec::Assign(payload) => &payload.options,
+            InstructionSpec::AssignWithSeed(payload) => &payload.options,
+            InstructionSpec::Allocate(payload) => &payload.options,
+            InstructionSpec::AllocateWithSeed(payload) => &payload.options,
+            InstructionSpec::TransferWithSeed(payload) => &payload.options,
+            InstructionSpec::CustomInstruction(payload) => &payload.options,
+            InstructionSpec::CreateLookupTable(payload) => &payload.options,
+            InstructionSpec::ExtendLookupTable(payload) => &payload.options,
+            InstructionSpec::FreezeLookupTable(payload) => &payload.options,
+            InstructionSpec::DeactivateLookupTable(payload) => &payload.options,
+            InstructionSpec::CloseLookupTable(payload) => &payload.options,
+            InstructionSpec::CreateNonceAccount(payload) => &payload.options,
+            InstructionSpec::AdvanceNonce(payload) => &payload.options,
+            InstructionSpec::WithdrawNonce(payload) => &payload.options,
+            InstructionSpec::AuthorizeNonce(payload) => &payload.options,
+            InstructionSpec::CreateStakeAccount(payload) => &payload.options,
+            InstructionSpec::CreateStakeAccountWithSeed(payload) => &payload.options,
+            InstructionSpec::DelegateStake(payload) => &payload.options,
+            InstructionSpec::DeactivateStake(payload) => &payload.options,
+            InstructionSpec::WithdrawStake(payload) => &payload.options,
+            InstructionSpec::SplitStake(payload) => &payload.options,
+            InstructionSpec::MergeStake(payload) => &payload.options,
+            InstructionSpec::AuthorizeStake(payload) => &payload.options,
+        }
+    }
+}
+
+/// `feePayer` is required here since no single step owns the transaction.
+#[derive(Deserialize)]
+pub struct BuildTransactionRequest {
+    pub instructions: Vec<InstructionSpec>,
+    #[serde(flatten)]
+    pub options: TransactionOptions,
 }
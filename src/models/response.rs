@@ -1,26 +1,25 @@
 use serde::Serialize;
+use serde_json::Value;
 
 #[derive(Serialize)]
 pub struct ApiResponse<T> {
     pub success: bool,
     pub data: Option<T>,
     pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
 }
 
 impl<T> ApiResponse<T> {
-    pub fn success(data: T) -> Self {
-        Self {
-            success: true,
-            data: Some(data),
-            error: None,
-        }
-    }
-
-    pub fn error(message: String) -> Self {
+    pub fn error(code: &'static str, message: String, field: Option<String>) -> Self {
         Self {
             success: false,
             data: None,
             error: Some(message),
+            code: Some(code),
+            field,
         }
     }
 }
@@ -42,18 +41,192 @@ pub struct InstructionResponse {
 pub struct TransactionResponse {
     pub transaction: String, // base64-encoded serialized transaction
     pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_lamports: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct LookupTableResponse {
+    #[serde(flatten)]
+    pub transaction: TransactionResponse,
+    pub lookup_table_address: String,
+}
+
+#[derive(Serialize)]
+pub struct CreateTokenResponse {
+    #[serde(flatten)]
+    pub transaction: TransactionResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata_address: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct CustomInstructionResponse {
+    #[serde(flatten)]
+    pub instruction: InstructionResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<TransactionResponse>,
+}
+
+#[derive(Serialize)]
+pub struct IdlSummaryResponse {
+    pub name: String,
+    pub program_id: Option<String>,
+    pub instructions: Vec<String>,
+    pub accounts: Vec<String>,
+    pub events: Vec<String>,
+    pub persisted: bool,
+}
+
+#[derive(Serialize)]
+pub struct IdlListResponse {
+    pub idls: Vec<String>,
+}
+
+/// An instruction account with the IDL name it was resolved for.
+#[derive(Serialize)]
+pub struct ResolvedAccount {
+    pub name: String,
+    pub pubkey: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(Serialize)]
+pub struct IdlInstructionResponse {
+    #[serde(flatten)]
+    pub transaction: TransactionResponse,
+    pub instruction_data: String,
+    pub accounts: Vec<ResolvedAccount>,
+}
+
+#[derive(Serialize)]
+pub struct DecodedAccountResponse {
+    pub account: String,
+    pub data: Value,
+}
+
+/// An event with the index of the log line it was emitted in. An event
+/// whose data doesn't match the IDL carries an `error` instead of `data`.
+#[derive(Serialize)]
+pub struct DecodedEvent {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub log_index: usize,
+}
+
+#[derive(Serialize)]
+pub struct DecodeEventsResponse {
+    pub events: Vec<DecodedEvent>,
+}
+
+/// Returned by endpoints that derive an account address from a seed.
+#[derive(Serialize)]
+pub struct SeedAccountResponse {
+    #[serde(flatten)]
+    pub transaction: TransactionResponse,
+    pub address: String,
 }
 
-// Alternative direct response formats for automated testing compatibility
 #[derive(Serialize)]
-pub struct DirectTransactionResponse {
+pub struct NftMintResponse {
+    #[serde(flatten)]
+    pub transaction: TransactionResponse,
+    pub token_account: String,
+    pub metadata_address: String,
+    pub master_edition_address: String,
+}
+
+/// `fee` is what the mint withholds from `amount`; the destination receives
+/// `net_amount`.
+#[derive(Serialize)]
+pub struct TransferWithFeeResponse {
+    #[serde(flatten)]
+    pub transaction: TransactionResponse,
+    pub fee: u64,
+    pub net_amount: u64,
+}
+
+#[derive(Serialize)]
+pub struct MessageHeaderResponse {
+    pub num_required_signatures: u8,
+    pub num_readonly_signed_accounts: u8,
+    pub num_readonly_unsigned_accounts: u8,
+}
+
+#[derive(Serialize)]
+pub struct AddressTableLookupResponse {
+    pub account_key: String,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+#[derive(Serialize)]
+pub struct TransactionSignature {
+    pub pubkey: String,
+    pub signature: String,
+    pub present: bool,
+}
+
+/// Transaction plus the state of each required signature after the server
+/// has added what it could.
+#[derive(Serialize)]
+pub struct SignedTransactionResponse {
     pub transaction: String,
+    pub signatures: Vec<TransactionSignature>,
+    pub missing_signers: Vec<String>,
+    pub complete: bool,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureStatus {
+    Valid,
+    Invalid,
+    /// All-zero signature left for a signer who hasn't signed yet
+    Placeholder,
 }
 
 #[derive(Serialize)]
-pub struct DirectKeypairResponse {
+pub struct SignatureVerification {
     pub pubkey: String,
-    pub secret: String,
+    pub signature: String,
+    pub status: SignatureStatus,
+}
+
+#[derive(Serialize)]
+pub struct TransactionVerificationResponse {
+    pub valid: bool,
+    pub signatures: Vec<SignatureVerification>,
+}
+
+#[derive(Serialize)]
+pub struct DecodedInstruction {
+    pub program_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program: Option<&'static str>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub instruction_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub info: Option<Value>,
+    pub accounts: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct DecodedTransactionResponse {
+    pub version: String,
+    pub header: MessageHeaderResponse,
+    pub account_keys: Vec<AccountMeta>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub address_table_lookups: Vec<AddressTableLookupResponse>,
+    pub recent_blockhash: String,
+    pub signatures: Vec<TransactionSignature>,
+    pub instructions: Vec<DecodedInstruction>,
 }
 
 #[derive(Serialize)]
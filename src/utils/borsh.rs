@@ -0,0 +1,174 @@
+use std::str::FromStr;
+use serde_json::Value;
+use solana_program::pubkey::Pubkey;
+use crate::models::ApiError;
+
+/// Field types accepted for Borsh-encoded instruction data. Written as
+/// `u64`, `pubkey`, `vec<u8>`, `vec<vec<string>>` and so on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BorshType {
+    U8,
+    U16,
+    U32,
+    U64,
+    I64,
+    Bool,
+    Pubkey,
+    String,
+    Vec(Box<BorshType>),
+}
+
+/// Deepest `vec<...>` nesting accepted in a field type.
+const MAX_VEC_NESTING: usize = 32;
+
+impl FromStr for BorshType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Unwrap the vec<...> layers in a loop, so the nesting depth of the
+        // input can't grow the stack
+        let mut s = s.trim();
+        let mut nesting = 0;
+        while let Some(inner) = s.strip_prefix("vec<").and_then(|rest| rest.strip_suffix('>')) {
+            nesting += 1;
+            if nesting > MAX_VEC_NESTING {
+                return Err(format!("vec<type> can nest at most {} levels deep", MAX_VEC_NESTING));
+            }
+            s = inner.trim();
+        }
+
+        let mut ty = match s {
+            "u8" => BorshType::U8,
+            "u16" => BorshType::U16,
+            "u32" => BorshType::U32,
+            "u64" => BorshType::U64,
+            "i64" => BorshType::I64,
+            "bool" => BorshType::Bool,
+            "pubkey" => BorshType::Pubkey,
+            "string" => BorshType::String,
+            _ => return Err(format!(
+                "unknown type '{}'; expected u8, u16, u32, u64, i64, bool, pubkey, string or vec<type>",
+                s
+            )),
+        };
+        for _ in 0..nesting {
+            ty = BorshType::Vec(Box::new(ty));
+        }
+        Ok(ty)
+    }
+}
+
+/// 64-bit values may come as strings, since JSON numbers lose precision
+/// above 2^53 in most clients.
+fn unsigned(field: &str, value: &Value) -> Result<u64, ApiError> {
+    match value {
+        Value::Number(number) => number.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| ApiError::invalid_field(field, "expected a non-negative integer"))
+}
+
+fn signed(field: &str, value: &Value) -> Result<i64, ApiError> {
+    match value {
+        Value::Number(number) => number.as_i64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| ApiError::invalid_field(field, "expected an integer"))
+}
+
+fn narrow<T: TryFrom<u64>>(field: &str, value: u64, type_name: &str) -> Result<T, ApiError> {
+    T::try_from(value).map_err(|_| ApiError::invalid_field(field, format!("{} is out of range for {}", value, type_name)))
+}
+
+/// Appends the Borsh encoding of `value` as `ty` to `out`: little-endian
+/// integers, one byte per bool, raw 32-byte pubkeys, and a u32 length in
+/// front of strings and vecs.
+pub fn encode_borsh(field: &str, ty: &BorshType, value: &Value, out: &mut Vec<u8>) -> Result<(), ApiError> {
+    match ty {
+        BorshType::U8 => out.push(narrow::<u8>(field, unsigned(field, value)?, "u8")?),
+        BorshType::U16 => out.extend(narrow::<u16>(field, unsigned(field, value)?, "u16")?.to_le_bytes()),
+        BorshType::U32 => out.extend(narrow::<u32>(field, unsigned(field, value)?, "u32")?.to_le_bytes()),
+        BorshType::U64 => out.extend(unsigned(field, value)?.to_le_bytes()),
+        BorshType::I64 => out.extend(signed(field, value)?.to_le_bytes()),
+        BorshType::Bool => {
+            let value = value.as_bool().ok_or_else(|| ApiError::invalid_field(field, "expected a bool"))?;
+            out.push(value as u8);
+        }
+        BorshType::Pubkey => {
+            let value = value.as_str().ok_or_else(|| ApiError::invalid_field(field, "expected a base58 pubkey"))?;
+            let pubkey = Pubkey::from_str(value).map_err(|e| ApiError::invalid_pubkey(field, e))?;
+            out.extend(pubkey.to_bytes());
+        }
+        BorshType::String => {
+            let value = value.as_str().ok_or_else(|| ApiError::invalid_field(field, "expected a string"))?;
+            out.extend((value.len() as u32).to_le_bytes());
+            out.extend(value.as_bytes());
+        }
+        BorshType::Vec(item) => {
+            let items = value.as_array().ok_or_else(|| ApiError::invalid_field(field, "expected an array"))?;
+            out.extend((items.len() as u32).to_le_bytes());
+            for (index, item_value) in items.iter().enumerate() {
+                encode_borsh(&format!("{}[{}]", field, index), item, item_value, out)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_nested_vec_types() {
+        assert_eq!(
+            " vec< vec<string> > ".parse::<BorshType>(),
+            Ok(BorshType::Vec(Box::new(BorshType::Vec(Box::new(BorshType::String))))),
+        );
+        assert!("vec<u128>".parse::<BorshType>().is_err());
+    }
+
+    #[test]
+    fn rejects_deep_vec_nesting() {
+        let nested = |levels: usize| format!("{}u8{}", "vec<".repeat(levels), ">".repeat(levels));
+        assert!(nested(MAX_VEC_NESTING).parse::<BorshType>().is_ok());
+        assert!(nested(MAX_VEC_NESTING + 1).parse::<BorshType>().is_err());
+        assert!(nested(200_000).parse::<BorshType>().is_err());
+    }
+
+    fn encode(ty: &str, value: Value) -> Result<Vec<u8>, ApiError> {
+        let mut out = Vec::new();
+        encode_borsh("value", &ty.parse().unwrap(), &value, &mut out).map(|_| out)
+    }
+
+    #[test]
+    fn encodes_borsh_layouts() {
+        assert_eq!(encode("u8", json!(3)).unwrap(), [3]);
+        assert_eq!(encode("u16", json!(258)).unwrap(), [2, 1]);
+        assert_eq!(encode("u32", json!(1)).unwrap(), [1, 0, 0, 0]);
+        assert_eq!(encode("u64", json!("18446744073709551615")).unwrap(), [0xff; 8]);
+        assert_eq!(encode("i64", json!(-1)).unwrap(), [0xff; 8]);
+        assert_eq!(encode("bool", json!(true)).unwrap(), [1]);
+        assert_eq!(encode("pubkey", json!("11111111111111111111111111111111")).unwrap(), [0; 32]);
+        assert_eq!(encode("string", json!("ab")).unwrap(), [2, 0, 0, 0, b'a', b'b']);
+        assert_eq!(
+            encode("vec<vec<u8>>", json!([[1], []])).unwrap(),
+            [2, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0],
+        );
+    }
+
+    #[test]
+    fn rejects_values_that_do_not_fit() {
+        assert!(encode("u8", json!(256)).is_err());
+        assert!(encode("u64", json!(-1)).is_err());
+        assert!(encode("bool", json!(1)).is_err());
+        assert!(encode("pubkey", json!("abc")).is_err());
+
+        let error = encode("vec<u16>", json!([1, 70000])).unwrap_err();
+        assert_eq!(error.field(), Some("value[1]"));
+    }
+}
@@ -1,20 +1,22 @@
 use anyhow::{anyhow, Result};
 use ed25519_dalek::{Signature, Keypair, PublicKey, SecretKey, Signer, Verifier};
-use solana_sdk::pubkey::Pubkey;
-use std::str::FromStr;
+use solana_sdk::{pubkey::Pubkey, signature::Signature as TransactionSignature};
 use base64::{engine::general_purpose::STANDARD, Engine};
+use crate::models::ApiError;
 
-pub fn decode_base58_pubkey(pubkey_str: &str) -> Result<Pubkey> {
-    Pubkey::from_str(pubkey_str).map_err(|e| anyhow!("Invalid pubkey: {}", e))
-}
-
-pub fn decode_base58_secret(secret_str: &str) -> Result<[u8; 32]> {
+pub fn decode_base58_secret(field: &str, secret_str: &str) -> Result<[u8; 32], ApiError> {
     let decoded = bs58::decode(secret_str)
         .into_vec()
-        .map_err(|e| anyhow!("Failed to decode base58 secret: {}", e))?;
+        .map_err(|e| ApiError::InvalidSecret {
+            field: field.to_string(),
+            reason: format!("failed to decode base58: {}", e),
+        })?;
 
     if decoded.len() != 32 {
-        return Err(anyhow!("Secret key must be 32 bytes, got {}", decoded.len()));
+        return Err(ApiError::InvalidSecretLength {
+            field: field.to_string(),
+            length: decoded.len(),
+        });
     }
 
     let mut bytes = [0u8; 32];
@@ -22,36 +24,42 @@ pub fn decode_base58_secret(secret_str: &str) -> Result<[u8; 32]> {
     Ok(bytes)
 }
 
-pub fn sign_message(message: &str, secret_bytes: &[u8; 32]) -> Result<String> {
+pub fn decode_base64_signature(field: &str, signature_b64: &str) -> Result<Signature, ApiError> {
+    let signature_bytes = STANDARD.decode(signature_b64)
+        .map_err(|e| ApiError::invalid_signature(field, format!("failed to decode base64: {}", e)))?;
+
+    Signature::from_bytes(&signature_bytes).map_err(|e| ApiError::invalid_signature(field, e))
+}
+
+pub fn keypair_from_secret(secret_bytes: &[u8; 32]) -> Result<Keypair> {
     let secret_key = SecretKey::from_bytes(secret_bytes)
         .map_err(|e| anyhow!("Invalid secret key: {}", e))?;
     let public_key = PublicKey::from(&secret_key);
-    let keypair = Keypair { secret: secret_key, public: public_key };
+    Ok(Keypair { secret: secret_key, public: public_key })
+}
+
+pub fn sign_message(message: &str, secret_bytes: &[u8; 32]) -> Result<String> {
+    let keypair = keypair_from_secret(secret_bytes)?;
 
     let signature = keypair.sign(message.as_bytes());
     Ok(STANDARD.encode(signature.to_bytes()))
 }
 
-pub fn verify_signature(message: &str, signature_b64: &str, pubkey_str: &str) -> Result<bool> {
-    let signature_bytes = STANDARD.decode(signature_b64)
-        .map_err(|e| anyhow!("Failed to decode base64 signature: {}", e))?;
-
-    let signature = Signature::from_bytes(&signature_bytes)
-        .map_err(|e| anyhow!("Invalid signature: {}", e))?;
-
-    let pubkey_bytes = bs58::decode(pubkey_str)
-        .into_vec()
-        .map_err(|e| anyhow!("Failed to decode base58 pubkey: {}", e))?;
+pub fn sign_bytes(message: &[u8], keypair: &Keypair) -> [u8; 64] {
+    keypair.sign(message).to_bytes()
+}
 
-    if pubkey_bytes.len() != 32 {
-        return Err(anyhow!("Public key must be 32 bytes"));
-    }
+pub fn verify_signature(message: &[u8], signature: &Signature, pubkey: &Pubkey) -> Result<bool> {
+    let public_key = PublicKey::from_bytes(pubkey.as_ref())
+        .map_err(|e| anyhow!("Invalid public key: {}", e))?;
 
-    let mut key_bytes = [0u8; 32];
-    key_bytes.copy_from_slice(&pubkey_bytes);
+    Ok(public_key.verify(message, signature).is_ok())
+}
 
-    let public_key = PublicKey::from_bytes(&key_bytes)
-        .map_err(|e| anyhow!("Invalid public key: {}", e))?;
+/// Checks one signature slot of a transaction against the serialized message.
+pub fn verify_transaction_signature(message: &[u8], signature: &TransactionSignature, pubkey: &Pubkey) -> Result<bool> {
+    let signature = Signature::from_bytes(signature.as_ref())
+        .map_err(|e| anyhow!("Invalid signature: {}", e))?;
 
-    Ok(public_key.verify(message.as_bytes(), &signature).is_ok())
+    verify_signature(message, &signature, pubkey)
 }
@@ -0,0 +1,397 @@
+use serde_json::{json, Map, Value};
+use solana_program::{
+    program_option::COption, pubkey, pubkey::Pubkey, system_instruction::SystemInstruction,
+    system_program,
+};
+use spl_token::instruction::{AuthorityType, TokenInstruction};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use crate::models::DecodedInstruction;
+
+const MEMO_V1_PROGRAM_ID: Pubkey = pubkey!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Decoded form of one instruction: a type name and its parameters, with the
+/// instruction's accounts attached under their role names.
+struct Parsed {
+    instruction_type: &'static str,
+    info: Map<String, Value>,
+}
+
+impl Parsed {
+    fn new(instruction_type: &'static str, params: Value, names: &[&str], accounts: &[String]) -> Self {
+        let mut info = match params {
+            Value::Object(map) => map,
+            _ => Map::new(),
+        };
+
+        for (name, account) in names.iter().zip(accounts) {
+            info.insert(name.to_string(), Value::String(account.clone()));
+        }
+
+        // Accounts past the named ones are multisig signers
+        if accounts.len() > names.len() {
+            info.insert("signers".to_string(), json!(accounts[names.len()..]));
+        }
+
+        Parsed { instruction_type, info }
+    }
+}
+
+/// `accounts` are the instruction's accounts already resolved to display
+/// strings, so lookup-table entries can be shown without RPC access.
+pub fn decode_instruction(program_id: &Pubkey, accounts: Vec<String>, data: &[u8]) -> DecodedInstruction {
+    let (program, parsed) = if *program_id == system_program::id() {
+        (Some("system"), decode_system(&accounts, data))
+    } else if *program_id == spl_token::id() {
+        (Some("spl-token"), decode_token(&accounts, data))
+    } else if *program_id == spl_token_2022::id() {
+        // Token-2022 keeps the original program's layout for the shared
+        // instructions; its extension instructions fall through as raw data
+        (Some("spl-token-2022"), decode_token(&accounts, data))
+    } else if *program_id == spl_associated_token_account::id() {
+        (Some("spl-associated-token-account"), decode_associated_token_account(&accounts, data))
+    } else if *program_id == solana_sdk::compute_budget::id() {
+        (Some("compute-budget"), decode_compute_budget(data))
+    } else if *program_id == MEMO_PROGRAM_ID || *program_id == MEMO_V1_PROGRAM_ID {
+        (Some("spl-memo"), decode_memo(&accounts, data))
+    } else {
+        (None, None)
+    };
+
+    match parsed {
+        Some(parsed) => DecodedInstruction {
+            program_id: program_id.to_string(),
+            program,
+            instruction_type: Some(parsed.instruction_type.to_string()),
+            info: Some(Value::Object(parsed.info)),
+            accounts,
+            data: None,
+        },
+        // Unknown program, or data the known program would reject
+        None => DecodedInstruction {
+            program_id: program_id.to_string(),
+            program,
+            instruction_type: None,
+            info: None,
+            accounts,
+            data: Some(STANDARD.encode(data)),
+        },
+    }
+}
+
+fn decode_system(accounts: &[String], data: &[u8]) -> Option<Parsed> {
+    let instruction: SystemInstruction = bincode::deserialize(data).ok()?;
+
+    let parsed = match instruction {
+        SystemInstruction::CreateAccount { lamports, space, owner } => Parsed::new(
+            "createAccount",
+            json!({ "lamports": lamports, "space": space, "owner": owner.to_string() }),
+            &["source", "newAccount"],
+            accounts,
+        ),
+        SystemInstruction::Assign { owner } => Parsed::new(
+            "assign",
+            json!({ "owner": owner.to_string() }),
+            &["account"],
+            accounts,
+        ),
+        SystemInstruction::Transfer { lamports } => Parsed::new(
+            "transfer",
+            json!({ "lamports": lamports }),
+            &["source", "destination"],
+            accounts,
+        ),
+        SystemInstruction::CreateAccountWithSeed { base, seed, lamports, space, owner } => Parsed::new(
+            "createAccountWithSeed",
+            json!({
+                "base": base.to_string(),
+                "seed": seed,
+                "lamports": lamports,
+                "space": space,
+                "owner": owner.to_string(),
+            }),
+            &["source", "newAccount", "baseAccount"],
+            accounts,
+        ),
+        SystemInstruction::AdvanceNonceAccount => Parsed::new(
+            "advanceNonce",
+            json!({}),
+            &["nonceAccount", "recentBlockhashesSysvar", "nonceAuthority"],
+            accounts,
+        ),
+        SystemInstruction::WithdrawNonceAccount(lamports) => Parsed::new(
+            "withdrawFromNonce",
+            json!({ "lamports": lamports }),
+            &["nonceAccount", "destination", "recentBlockhashesSysvar", "rentSysvar", "nonceAuthority"],
+            accounts,
+        ),
+        SystemInstruction::InitializeNonceAccount(authority) => Parsed::new(
+            "initializeNonce",
+            json!({ "nonceAuthority": authority.to_string() }),
+            &["nonceAccount", "recentBlockhashesSysvar", "rentSysvar"],
+            accounts,
+        ),
+        SystemInstruction::AuthorizeNonceAccount(new_authority) => Parsed::new(
+            "authorizeNonce",
+            json!({ "newAuthority": new_authority.to_string() }),
+            &["nonceAccount", "nonceAuthority"],
+            accounts,
+        ),
+        SystemInstruction::Allocate { space } => Parsed::new(
+            "allocate",
+            json!({ "space": space }),
+            &["account"],
+            accounts,
+        ),
+        SystemInstruction::AllocateWithSeed { base, seed, space, owner } => Parsed::new(
+            "allocateWithSeed",
+            json!({ "base": base.to_string(), "seed": seed, "space": space, "owner": owner.to_string() }),
+            &["account", "baseAccount"],
+            accounts,
+        ),
+        SystemInstruction::AssignWithSeed { base, seed, owner } => Parsed::new(
+            "assignWithSeed",
+            json!({ "base": base.to_string(), "seed": seed, "owner": owner.to_string() }),
+            &["account", "baseAccount"],
+            accounts,
+        ),
+        SystemInstruction::TransferWithSeed { lamports, from_seed, from_owner } => Parsed::new(
+            "transferWithSeed",
+            json!({ "lamports": lamports, "sourceSeed": from_seed, "sourceOwner": from_owner.to_string() }),
+            &["source", "sourceBase", "destination"],
+            accounts,
+        ),
+        SystemInstruction::UpgradeNonceAccount => Parsed::new(
+            "upgradeNonce",
+            json!({}),
+            &["nonceAccount"],
+            accounts,
+        ),
+    };
+
+    Some(parsed)
+}
+
+fn optional_pubkey(value: COption<Pubkey>) -> Value {
+    match value {
+        COption::Some(pubkey) => Value::String(pubkey.to_string()),
+        COption::None => Value::Null,
+    }
+}
+
+fn authority_type_name(authority_type: &AuthorityType) -> &'static str {
+    match authority_type {
+        AuthorityType::MintTokens => "mintTokens",
+        AuthorityType::FreezeAccount => "freezeAccount",
+        AuthorityType::AccountOwner => "accountOwner",
+        AuthorityType::CloseAccount => "closeAccount",
+    }
+}
+
+fn decode_token(accounts: &[String], data: &[u8]) -> Option<Parsed> {
+    let instruction = TokenInstruction::unpack(data).ok()?;
+
+    let parsed = match instruction {
+        TokenInstruction::InitializeMint { decimals, mint_authority, freeze_authority } => Parsed::new(
+            "initializeMint",
+            json!({
+                "decimals": decimals,
+                "mintAuthority": mint_authority.to_string(),
+                "freezeAuthority": optional_pubkey(freeze_authority),
+            }),
+            &["mint", "rentSysvar"],
+            accounts,
+        ),
+        TokenInstruction::InitializeMint2 { decimals, mint_authority, freeze_authority } => Parsed::new(
+            "initializeMint2",
+            json!({
+                "decimals": decimals,
+                "mintAuthority": mint_authority.to_string(),
+                "freezeAuthority": optional_pubkey(freeze_authority),
+            }),
+            &["mint"],
+            accounts,
+        ),
+        TokenInstruction::InitializeAccount => Parsed::new(
+            "initializeAccount",
+            json!({}),
+            &["account", "mint", "owner", "rentSysvar"],
+            accounts,
+        ),
+        TokenInstruction::InitializeAccount2 { owner } => Parsed::new(
+            "initializeAccount2",
+            json!({ "owner": owner.to_string() }),
+            &["account", "mint", "rentSysvar"],
+            accounts,
+        ),
+        TokenInstruction::InitializeAccount3 { owner } => Parsed::new(
+            "initializeAccount3",
+            json!({ "owner": owner.to_string() }),
+            &["account", "mint"],
+            accounts,
+        ),
+        TokenInstruction::InitializeMultisig { m } => {
+            let mut parsed = Parsed::new("initializeMultisig", json!({ "m": m }), &["multisig", "rentSysvar"], accounts);
+            parsed.info.insert("signers".to_string(), json!(accounts.get(2..).unwrap_or_default()));
+            parsed
+        }
+        TokenInstruction::InitializeMultisig2 { m } => {
+            let mut parsed = Parsed::new("initializeMultisig2", json!({ "m": m }), &["multisig"], accounts);
+            parsed.info.insert("signers".to_string(), json!(accounts.get(1..).unwrap_or_default()));
+            parsed
+        }
+        TokenInstruction::Transfer { amount } => Parsed::new(
+            "transfer",
+            json!({ "amount": amount }),
+            &["source", "destination", "authority"],
+            accounts,
+        ),
+        TokenInstruction::TransferChecked { amount, decimals } => Parsed::new(
+            "transferChecked",
+            json!({ "amount": amount, "decimals": decimals }),
+            &["source", "mint", "destination", "authority"],
+            accounts,
+        ),
+        TokenInstruction::Approve { amount } => Parsed::new(
+            "approve",
+            json!({ "amount": amount }),
+            &["source", "delegate", "owner"],
+            accounts,
+        ),
+        TokenInstruction::ApproveChecked { amount, decimals } => Parsed::new(
+            "approveChecked",
+            json!({ "amount": amount, "decimals": decimals }),
+            &["source", "mint", "delegate", "owner"],
+            accounts,
+        ),
+        TokenInstruction::Revoke => Parsed::new(
+            "revoke",
+            json!({}),
+            &["source", "owner"],
+            accounts,
+        ),
+        TokenInstruction::SetAuthority { authority_type, new_authority } => Parsed::new(
+            "setAuthority",
+            json!({
+                "authorityType": authority_type_name(&authority_type),
+                "newAuthority": optional_pubkey(new_authority),
+            }),
+            &["account", "authority"],
+            accounts,
+        ),
+        TokenInstruction::MintTo { amount } => Parsed::new(
+            "mintTo",
+            json!({ "amount": amount }),
+            &["mint", "account", "mintAuthority"],
+            accounts,
+        ),
+        TokenInstruction::MintToChecked { amount, decimals } => Parsed::new(
+            "mintToChecked",
+            json!({ "amount": amount, "decimals": decimals }),
+            &["mint", "account", "mintAuthority"],
+            accounts,
+        ),
+        TokenInstruction::Burn { amount } => Parsed::new(
+            "burn",
+            json!({ "amount": amount }),
+            &["account", "mint", "authority"],
+            accounts,
+        ),
+        TokenInstruction::BurnChecked { amount, decimals } => Parsed::new(
+            "burnChecked",
+            json!({ "amount": amount, "decimals": decimals }),
+            &["account", "mint", "authority"],
+            accounts,
+        ),
+        TokenInstruction::CloseAccount => Parsed::new(
+            "closeAccount",
+            json!({}),
+            &["account", "destination", "owner"],
+            accounts,
+        ),
+        TokenInstruction::FreezeAccount => Parsed::new(
+            "freezeAccount",
+            json!({}),
+            &["account", "mint", "freezeAuthority"],
+            accounts,
+        ),
+        TokenInstruction::ThawAccount => Parsed::new(
+            "thawAccount",
+            json!({}),
+            &["account", "mint", "freezeAuthority"],
+            accounts,
+        ),
+        TokenInstruction::SyncNative => Parsed::new(
+            "syncNative",
+            json!({}),
+            &["account"],
+            accounts,
+        ),
+        TokenInstruction::GetAccountDataSize => Parsed::new(
+            "getAccountDataSize",
+            json!({}),
+            &["mint"],
+            accounts,
+        ),
+        TokenInstruction::InitializeImmutableOwner => Parsed::new(
+            "initializeImmutableOwner",
+            json!({}),
+            &["account"],
+            accounts,
+        ),
+        TokenInstruction::AmountToUiAmount { amount } => Parsed::new(
+            "amountToUiAmount",
+            json!({ "amount": amount }),
+            &["mint"],
+            accounts,
+        ),
+        TokenInstruction::UiAmountToAmount { ui_amount } => Parsed::new(
+            "uiAmountToAmount",
+            json!({ "uiAmount": ui_amount }),
+            &["mint"],
+            accounts,
+        ),
+    };
+
+    Some(parsed)
+}
+
+fn decode_associated_token_account(accounts: &[String], data: &[u8]) -> Option<Parsed> {
+    const CREATE_ACCOUNTS: &[&str] = &["source", "account", "wallet", "mint", "systemProgram", "tokenProgram"];
+
+    // An empty payload is the original form of Create
+    let parsed = match data.first() {
+        None | Some(0) => Parsed::new("create", json!({}), CREATE_ACCOUNTS, accounts),
+        Some(1) => Parsed::new("createIdempotent", json!({}), CREATE_ACCOUNTS, accounts),
+        Some(2) => Parsed::new(
+            "recoverNested",
+            json!({}),
+            &["nestedSource", "nestedMint", "destination", "nestedOwner", "ownerMint", "wallet", "tokenProgram"],
+            accounts,
+        ),
+        Some(_) => return None,
+    };
+
+    Some(parsed)
+}
+
+fn decode_compute_budget(data: &[u8]) -> Option<Parsed> {
+    let (tag, rest) = data.split_first()?;
+    let u32_arg = || rest.get(..4).map(|b| u32::from_le_bytes(b.try_into().unwrap()));
+    let u64_arg = || rest.get(..8).map(|b| u64::from_le_bytes(b.try_into().unwrap()));
+
+    let parsed = match tag {
+        1 => Parsed::new("requestHeapFrame", json!({ "bytes": u32_arg()? }), &[], &[]),
+        2 => Parsed::new("setComputeUnitLimit", json!({ "units": u32_arg()? }), &[], &[]),
+        3 => Parsed::new("setComputeUnitPrice", json!({ "microLamports": u64_arg()? }), &[], &[]),
+        4 => Parsed::new("setLoadedAccountsDataSizeLimit", json!({ "bytes": u32_arg()? }), &[], &[]),
+        _ => return None,
+    };
+
+    Some(parsed)
+}
+
+fn decode_memo(accounts: &[String], data: &[u8]) -> Option<Parsed> {
+    let memo = std::str::from_utf8(data).ok()?;
+    Some(Parsed::new("memo", json!({ "memo": memo }), &[], accounts))
+}
@@ -0,0 +1,45 @@
+use axum::{
+    async_trait,
+    extract::{rejection::JsonRejection, FromRequest, Request},
+    Json,
+};
+use serde::de::DeserializeOwned;
+use crate::models::ApiError;
+
+/// JSON body extractor that reports rejections through the `ApiError` envelope,
+/// including the serde path of the field that failed to deserialize.
+pub struct ApiJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<serde_json::Value>::from_request(req, state)
+            .await
+            .map_err(handle_json_rejection)?;
+
+        serde_path_to_error::deserialize(value)
+            .map(ApiJson)
+            .map_err(|e| {
+                let path = e.path().to_string();
+                ApiError::InvalidJson {
+                    path: if path == "." { None } else { Some(path) },
+                    reason: e.into_inner().to_string(),
+                }
+            })
+    }
+}
+
+pub fn handle_json_rejection(rejection: JsonRejection) -> ApiError {
+    match rejection {
+        JsonRejection::MissingJsonContentType(_) => ApiError::MissingContentType,
+        JsonRejection::JsonSyntaxError(e) => ApiError::InvalidJson { path: None, reason: e.body_text() },
+        JsonRejection::JsonDataError(e) => ApiError::InvalidJson { path: None, reason: e.body_text() },
+        other => ApiError::InvalidJson { path: None, reason: other.body_text() },
+    }
+}
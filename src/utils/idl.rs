@@ -0,0 +1,1100 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use solana_program::{hash::hashv, pubkey::Pubkey};
+use crate::models::ApiError;
+
+/// An Anchor IDL. Both the 0.30+ format and the older one (`isMut` /
+/// `isSigner`, `publicKey`, `metadata.address`, no discriminators) parse
+/// into this shape.
+pub struct Idl {
+    pub program_id: Option<Pubkey>,
+    pub instructions: Vec<IdlInstruction>,
+    pub accounts: Vec<IdlDiscriminated>,
+    pub events: Vec<IdlDiscriminated>,
+    pub types: HashMap<String, IdlTypeDefTy>,
+}
+
+pub struct IdlInstruction {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+    pub accounts: Vec<IdlAccountItem>,
+    pub args: Vec<IdlField>,
+}
+
+/// An account or event: its layout is the type of the same name.
+pub struct IdlDiscriminated {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "Value")]
+pub enum IdlType {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    F32,
+    U64,
+    I64,
+    F64,
+    U128,
+    I128,
+    Bytes,
+    String,
+    Pubkey,
+    Option(Box<IdlType>),
+    COption(Box<IdlType>),
+    Vec(Box<IdlType>),
+    Array(Box<IdlType>, usize),
+    Defined(String),
+}
+
+impl TryFrom<Value> for IdlType {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(name) => match name.as_str() {
+                "bool" => Ok(IdlType::Bool),
+                "u8" => Ok(IdlType::U8),
+                "i8" => Ok(IdlType::I8),
+                "u16" => Ok(IdlType::U16),
+                "i16" => Ok(IdlType::I16),
+                "u32" => Ok(IdlType::U32),
+                "i32" => Ok(IdlType::I32),
+                "f32" => Ok(IdlType::F32),
+                "u64" => Ok(IdlType::U64),
+                "i64" => Ok(IdlType::I64),
+                "f64" => Ok(IdlType::F64),
+                "u128" => Ok(IdlType::U128),
+                "i128" => Ok(IdlType::I128),
+                "bytes" => Ok(IdlType::Bytes),
+                "string" => Ok(IdlType::String),
+                "pubkey" | "publicKey" => Ok(IdlType::Pubkey),
+                other => Err(format!("unsupported type '{}'", other)),
+            },
+            Value::Object(map) if map.len() == 1 => {
+                let Some((kind, inner)) = map.into_iter().next() else {
+                    return Err("empty type".to_string());
+                };
+                match kind.as_str() {
+                    "option" => Ok(IdlType::Option(Box::new(inner.try_into()?))),
+                    "coption" => Ok(IdlType::COption(Box::new(inner.try_into()?))),
+                    "vec" => Ok(IdlType::Vec(Box::new(inner.try_into()?))),
+                    "array" => {
+                        let Value::Array(mut parts) = inner else {
+                            return Err("array type must be [type, length]".to_string());
+                        };
+                        let (Some(length), Some(item), true) = (parts.pop(), parts.pop(), parts.is_empty()) else {
+                            return Err("array type must be [type, length]".to_string());
+                        };
+                        let length = length.as_u64()
+                            .ok_or_else(|| "generic array lengths are not supported".to_string())?;
+                        Ok(IdlType::Array(Box::new(item.try_into()?), length as usize))
+                    }
+                    "defined" => match inner {
+                        Value::String(name) => Ok(IdlType::Defined(name)),
+                        Value::Object(defined) => {
+                            if defined.get("generics").and_then(Value::as_array).is_some_and(|g| !g.is_empty()) {
+                                return Err("generic types are not supported".to_string());
+                            }
+                            defined.get("name").and_then(Value::as_str)
+                                .map(|name| IdlType::Defined(name.to_string()))
+                                .ok_or_else(|| "defined type without a name".to_string())
+                        }
+                        _ => Err("defined type without a name".to_string()),
+                    },
+                    "generic" => Err("generic types are not supported".to_string()),
+                    other => Err(format!("unsupported type '{}'", other)),
+                }
+            }
+            other => Err(format!("unsupported type {}", other)),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+pub struct IdlField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlType,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+pub enum IdlDefinedFields {
+    Named(Vec<IdlField>),
+    Tuple(Vec<IdlType>),
+}
+
+#[derive(Clone, Deserialize)]
+pub struct IdlEnumVariant {
+    pub name: String,
+    pub fields: Option<IdlDefinedFields>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlTypeDefTy {
+    Struct { fields: Option<IdlDefinedFields> },
+    Enum { variants: Vec<IdlEnumVariant> },
+    Type { alias: IdlType },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum IdlAccountItem {
+    Composite(IdlCompositeAccount),
+    Single(IdlAccount),
+}
+
+/// A group of accounts from a nested `#[derive(Accounts)]` struct.
+#[derive(Deserialize)]
+pub struct IdlCompositeAccount {
+    pub name: String,
+    pub accounts: Vec<IdlAccountItem>,
+}
+
+#[derive(Deserialize)]
+pub struct IdlAccount {
+    pub name: String,
+    #[serde(default, alias = "isMut")]
+    pub writable: bool,
+    #[serde(default, alias = "isSigner")]
+    pub signer: bool,
+    #[serde(default, alias = "isOptional")]
+    pub optional: bool,
+    pub address: Option<String>,
+    pub pda: Option<IdlPda>,
+}
+
+#[derive(Deserialize)]
+pub struct IdlPda {
+    pub seeds: Vec<IdlSeed>,
+    pub program: Option<IdlSeed>,
+}
+
+/// `path` names an instruction argument or another account, optionally
+/// followed by `.field`.
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlSeed {
+    Const {
+        #[serde(rename = "type")]
+        ty: Option<IdlType>,
+        value: Value,
+    },
+    Arg { path: String },
+    Account { path: String },
+}
+
+#[derive(Deserialize)]
+struct RawIdl {
+    address: Option<String>,
+    metadata: Option<RawMetadata>,
+    #[serde(default)]
+    instructions: Vec<RawInstruction>,
+    #[serde(default)]
+    accounts: Vec<RawDefinition>,
+    #[serde(default)]
+    events: Vec<RawDefinition>,
+    #[serde(default)]
+    types: Vec<RawTypeDef>,
+}
+
+#[derive(Deserialize)]
+struct RawMetadata {
+    address: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawInstruction {
+    name: String,
+    discriminator: Option<Vec<u8>>,
+    #[serde(default)]
+    accounts: Vec<IdlAccountItem>,
+    #[serde(default)]
+    args: Vec<IdlField>,
+}
+
+/// 0.30+ IDLs give accounts and events a discriminator and keep their layout
+/// in `types`; older ones inline it (`type` for accounts, `fields` for events).
+#[derive(Deserialize)]
+struct RawDefinition {
+    name: String,
+    discriminator: Option<Vec<u8>>,
+    #[serde(rename = "type")]
+    ty: Option<IdlTypeDefTy>,
+    fields: Option<Vec<IdlField>>,
+}
+
+#[derive(Deserialize)]
+struct RawTypeDef {
+    name: String,
+    #[serde(rename = "type")]
+    ty: IdlTypeDefTy,
+}
+
+/// Anchor's discriminator for IDLs that don't list one: the first 8 bytes
+/// of sha256("<namespace>:<name>").
+fn sighash(namespace: &str, name: &str) -> Vec<u8> {
+    hashv(&[namespace.as_bytes(), b":", name.as_bytes()]).to_bytes()[..8].to_vec()
+}
+
+/// camelCase to snake_case the way Anchor does for instruction names.
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            let previous = chars[index - 1];
+            let next_is_lower = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase() || previous.is_ascii_digit() || (previous.is_uppercase() && next_is_lower) {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Names match across the IDL's and the caller's casing, so `vaultBump`,
+/// `vault_bump` and `VaultBump` are the same.
+pub fn names_match(a: &str, b: &str) -> bool {
+    let normalize = |name: &str| name.chars().filter(|c| *c != '_').flat_map(char::to_lowercase).collect::<String>();
+    normalize(a) == normalize(b)
+}
+
+pub fn find_named<'a>(map: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    map.get(name).or_else(|| map.iter().find(|(key, _)| names_match(key, name)).map(|(_, value)| value))
+}
+
+pub fn parse_idl(value: Value) -> Result<Idl, ApiError> {
+    let raw: RawIdl = serde_path_to_error::deserialize(value).map_err(|e| {
+        let path = e.path().to_string();
+        ApiError::InvalidJson {
+            path: if path == "." { None } else { Some(path) },
+            reason: e.into_inner().to_string(),
+        }
+    })?;
+
+    let program_id = raw.address.or(raw.metadata.and_then(|metadata| metadata.address))
+        .map(|address| Pubkey::from_str(&address).map_err(|e| ApiError::invalid_pubkey("address", e)))
+        .transpose()?;
+
+    let mut types: HashMap<String, IdlTypeDefTy> = raw.types
+        .into_iter()
+        .map(|definition| (definition.name, definition.ty))
+        .collect();
+
+    let accounts = raw.accounts
+        .into_iter()
+        .map(|account| {
+            if let Some(ty) = account.ty {
+                types.insert(account.name.clone(), ty);
+            }
+            IdlDiscriminated {
+                discriminator: account.discriminator.unwrap_or_else(|| sighash("account", &account.name)),
+                name: account.name,
+            }
+        })
+        .collect();
+
+    let events = raw.events
+        .into_iter()
+        .map(|event| {
+            if let Some(fields) = event.fields {
+                types.insert(event.name.clone(), IdlTypeDefTy::Struct { fields: Some(IdlDefinedFields::Named(fields)) });
+            }
+            IdlDiscriminated {
+                discriminator: event.discriminator.unwrap_or_else(|| sighash("event", &event.name)),
+                name: event.name,
+            }
+        })
+        .collect();
+
+    let instructions = raw.instructions
+        .into_iter()
+        .map(|instruction| IdlInstruction {
+            discriminator: instruction.discriminator
+                .unwrap_or_else(|| sighash("global", &to_snake_case(&instruction.name))),
+            name: instruction.name,
+            accounts: instruction.accounts,
+            args: instruction.args,
+        })
+        .collect();
+
+    check_type_cycles(&types)?;
+
+    Ok(Idl { program_id, instructions, accounts, events, types })
+}
+
+impl Idl {
+    pub fn instruction(&self, name: &str) -> Option<&IdlInstruction> {
+        self.instructions.iter().find(|instruction| names_match(&instruction.name, name))
+    }
+
+    pub fn type_def(&self, field: &str, name: &str) -> Result<&IdlTypeDefTy, ApiError> {
+        self.types.get(name)
+            .ok_or_else(|| ApiError::invalid_field(field, format!("IDL has no type named '{}'", name)))
+    }
+}
+
+/// How deeply values may nest. Recursive types (`Option<Box<Self>>`, enums
+/// holding themselves) are legal, so this is what bounds the recursion.
+const MAX_TYPE_DEPTH: usize = 64;
+
+const MAX_DECODED_VALUES: usize = 1 << 20;
+
+fn check_depth(field: &str, depth: usize) -> Result<(), ApiError> {
+    if depth > MAX_TYPE_DEPTH {
+        return Err(ApiError::invalid_field(field, format!("nested more than {} levels deep", MAX_TYPE_DEPTH)));
+    }
+    Ok(())
+}
+
+/// Defined types that `ty` holds inline, i.e. not behind a length or tag
+/// that data has to supply.
+fn inline_types<'a>(ty: &'a IdlType, out: &mut Vec<&'a str>) {
+    match ty {
+        IdlType::Defined(name) => out.push(name),
+        IdlType::Array(inner, _) | IdlType::COption(inner) => inline_types(inner, out),
+        _ => {}
+    }
+}
+
+/// The defined types a type definition holds inline.
+fn inline_type_names(definition: &IdlTypeDefTy) -> Vec<&str> {
+    let mut inline = Vec::new();
+    match definition {
+        IdlTypeDefTy::Type { alias } => inline_types(alias, &mut inline),
+        IdlTypeDefTy::Struct { fields: Some(IdlDefinedFields::Named(fields)) } => {
+            fields.iter().for_each(|field| inline_types(&field.ty, &mut inline));
+        }
+        IdlTypeDefTy::Struct { fields: Some(IdlDefinedFields::Tuple(fields)) } => {
+            fields.iter().for_each(|ty| inline_types(ty, &mut inline));
+        }
+        // An enum's tag byte comes from data, so enums may recurse
+        IdlTypeDefTy::Struct { fields: None } | IdlTypeDefTy::Enum { .. } => {}
+    }
+    inline
+}
+
+/// Rejects types that contain themselves inline, like an alias of itself or
+/// a struct with a field of its own type. Those have no finite encoding.
+/// Types chained inline deeper than any value may nest are rejected too; the
+/// walk keeps its own stack so an uploaded chain can't exhaust the thread's.
+fn check_type_cycles(types: &HashMap<String, IdlTypeDefTy>) -> Result<(), ApiError> {
+    let mut visiting = HashSet::new();
+    let mut done = HashSet::new();
+
+    for (root, definition) in types {
+        if done.contains(root.as_str()) {
+            continue;
+        }
+
+        visiting.insert(root.as_str());
+        let mut stack = vec![(root.as_str(), inline_type_names(definition), 0)];
+        while let Some((name, inline, next)) = stack.last_mut() {
+            let Some(&inner) = inline.get(*next) else {
+                visiting.remove(*name);
+                done.insert(*name);
+                stack.pop();
+                continue;
+            };
+            *next += 1;
+
+            if visiting.contains(inner) {
+                return Err(ApiError::invalid_field("types", format!("type '{}' contains itself", inner)));
+            }
+            if done.contains(inner) {
+                continue;
+            }
+            if let Some((inner, definition)) = types.get_key_value(inner) {
+                if stack.len() >= MAX_TYPE_DEPTH {
+                    return Err(ApiError::invalid_field(
+                        "types",
+                        format!("type '{}' nests more than {} levels deep", root, MAX_TYPE_DEPTH),
+                    ));
+                }
+                visiting.insert(inner.as_str());
+                stack.push((inner.as_str(), inline_type_names(definition), 0));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn integer(field: &str, value: &Value) -> Result<i128, ApiError> {
+    match value {
+        Value::Number(number) => number.as_i64().map(i128::from).or_else(|| number.as_u64().map(i128::from)),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| ApiError::invalid_field(field, "expected an integer"))
+}
+
+fn fit<T: TryFrom<i128>>(field: &str, value: i128, type_name: &str) -> Result<T, ApiError> {
+    T::try_from(value).map_err(|_| ApiError::invalid_field(field, format!("{} is out of range for {}", value, type_name)))
+}
+
+fn float(field: &str, value: &Value) -> Result<f64, ApiError> {
+    value.as_f64().ok_or_else(|| ApiError::invalid_field(field, "expected a number"))
+}
+
+/// Byte values as an array of numbers or a base64 string.
+fn bytes(field: &str, value: &Value) -> Result<Vec<u8>, ApiError> {
+    match value {
+        Value::String(s) => STANDARD.decode(s)
+            .map_err(|e| ApiError::invalid_field(field, format!("failed to decode base64: {}", e))),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let field = format!("{}[{}]", field, index);
+                fit::<u8>(&field, integer(&field, item)?, "u8")
+            })
+            .collect(),
+        _ => Err(ApiError::invalid_field(field, "expected an array of bytes or a base64 string")),
+    }
+}
+
+fn encode_fields(idl: &Idl, field: &str, fields: &IdlDefinedFields, value: &Value, out: &mut Vec<u8>, depth: usize) -> Result<(), ApiError> {
+    match fields {
+        IdlDefinedFields::Named(fields) if fields.is_empty() => {}
+        IdlDefinedFields::Tuple(types) if types.is_empty() => {}
+        IdlDefinedFields::Named(fields) => {
+            let object = value.as_object()
+                .ok_or_else(|| ApiError::invalid_field(field, "expected an object"))?;
+            for named in fields {
+                let field = format!("{}.{}", field, named.name);
+                let value = find_named(object, &named.name)
+                    .ok_or_else(|| ApiError::invalid_field(&field, "missing"))?;
+                encode_value(idl, &field, &named.ty, value, out, depth + 1)?;
+            }
+        }
+        IdlDefinedFields::Tuple(types) => {
+            let items = value.as_array()
+                .filter(|items| items.len() == types.len())
+                .ok_or_else(|| ApiError::invalid_field(field, format!("expected an array of {} values", types.len())))?;
+            for (index, (ty, item)) in types.iter().zip(items).enumerate() {
+                encode_value(idl, &format!("{}[{}]", field, index), ty, item, out, depth + 1)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Borsh-encodes a JSON value as the IDL type `ty`. Enums are written as
+/// `"Variant"` or `{ "Variant": fields }`, and `null` is `None`.
+pub fn encode_idl_value(idl: &Idl, field: &str, ty: &IdlType, value: &Value, out: &mut Vec<u8>) -> Result<(), ApiError> {
+    encode_value(idl, field, ty, value, out, 0)
+}
+
+fn encode_value(idl: &Idl, field: &str, ty: &IdlType, value: &Value, out: &mut Vec<u8>, depth: usize) -> Result<(), ApiError> {
+    check_depth(field, depth)?;
+    match ty {
+        IdlType::Bool => {
+            let value = value.as_bool().ok_or_else(|| ApiError::invalid_field(field, "expected a bool"))?;
+            out.push(value as u8);
+        }
+        IdlType::U8 => out.push(fit::<u8>(field, integer(field, value)?, "u8")?),
+        IdlType::I8 => out.extend(fit::<i8>(field, integer(field, value)?, "i8")?.to_le_bytes()),
+        IdlType::U16 => out.extend(fit::<u16>(field, integer(field, value)?, "u16")?.to_le_bytes()),
+        IdlType::I16 => out.extend(fit::<i16>(field, integer(field, value)?, "i16")?.to_le_bytes()),
+        IdlType::U32 => out.extend(fit::<u32>(field, integer(field, value)?, "u32")?.to_le_bytes()),
+        IdlType::I32 => out.extend(fit::<i32>(field, integer(field, value)?, "i32")?.to_le_bytes()),
+        IdlType::U64 => out.extend(fit::<u64>(field, integer(field, value)?, "u64")?.to_le_bytes()),
+        IdlType::I64 => out.extend(fit::<i64>(field, integer(field, value)?, "i64")?.to_le_bytes()),
+        IdlType::I128 => out.extend(integer(field, value)?.to_le_bytes()),
+        IdlType::U128 => {
+            // u128 goes past i128::MAX, so parse it on its own
+            let value = match value {
+                Value::Number(number) => number.as_u64().map(u128::from),
+                Value::String(s) => s.parse().ok(),
+                _ => None,
+            }
+            .ok_or_else(|| ApiError::invalid_field(field, "expected a non-negative integer"))?;
+            out.extend(value.to_le_bytes());
+        }
+        IdlType::F32 => out.extend((float(field, value)? as f32).to_le_bytes()),
+        IdlType::F64 => out.extend(float(field, value)?.to_le_bytes()),
+        IdlType::Bytes => {
+            let value = bytes(field, value)?;
+            out.extend((value.len() as u32).to_le_bytes());
+            out.extend(value);
+        }
+        IdlType::String => {
+            let value = value.as_str().ok_or_else(|| ApiError::invalid_field(field, "expected a string"))?;
+            out.extend((value.len() as u32).to_le_bytes());
+            out.extend(value.as_bytes());
+        }
+        IdlType::Pubkey => {
+            let value = value.as_str().ok_or_else(|| ApiError::invalid_field(field, "expected a base58 pubkey"))?;
+            let pubkey = Pubkey::from_str(value).map_err(|e| ApiError::invalid_pubkey(field, e))?;
+            out.extend(pubkey.to_bytes());
+        }
+        IdlType::Option(inner) => {
+            if value.is_null() {
+                out.push(0);
+            } else {
+                out.push(1);
+                encode_value(idl, field, inner, value, out, depth + 1)?;
+            }
+        }
+        IdlType::COption(inner) => {
+            if value.is_null() {
+                out.extend(0u32.to_le_bytes());
+            } else {
+                out.extend(1u32.to_le_bytes());
+                encode_value(idl, field, inner, value, out, depth + 1)?;
+            }
+        }
+        IdlType::Vec(inner) => {
+            let items = value.as_array().ok_or_else(|| ApiError::invalid_field(field, "expected an array"))?;
+            out.extend((items.len() as u32).to_le_bytes());
+            for (index, item) in items.iter().enumerate() {
+                encode_value(idl, &format!("{}[{}]", field, index), inner, item, out, depth + 1)?;
+            }
+        }
+        IdlType::Array(inner, length) => {
+            let items = value.as_array()
+                .filter(|items| items.len() == *length)
+                .ok_or_else(|| ApiError::invalid_field(field, format!("expected an array of {} values", length)))?;
+            for (index, item) in items.iter().enumerate() {
+                encode_value(idl, &format!("{}[{}]", field, index), inner, item, out, depth + 1)?;
+            }
+        }
+        IdlType::Defined(name) => match idl.type_def(field, name)? {
+            IdlTypeDefTy::Struct { fields: Some(fields) } => encode_fields(idl, field, fields, value, out, depth)?,
+            IdlTypeDefTy::Struct { fields: None } => {}
+            IdlTypeDefTy::Type { alias } => encode_value(idl, field, alias, value, out, depth + 1)?,
+            IdlTypeDefTy::Enum { variants } => {
+                let (variant_name, fields_value) = match value {
+                    Value::String(variant) => (variant.as_str(), &Value::Null),
+                    Value::Object(object) if object.len() == 1 => {
+                        let Some((variant, fields)) = object.iter().next() else {
+                            return Err(ApiError::invalid_field(field, "expected an enum variant"));
+                        };
+                        (variant.as_str(), fields)
+                    }
+                    _ => return Err(ApiError::invalid_field(field, "expected \"Variant\" or { \"Variant\": fields }")),
+                };
+                let (index, variant) = variants
+                    .iter()
+                    .enumerate()
+                    .find(|(_, variant)| names_match(&variant.name, variant_name))
+                    .ok_or_else(|| ApiError::invalid_field(field, format!("'{}' is not a variant of {}", variant_name, name)))?;
+                out.push(index as u8);
+                if let Some(fields) = &variant.fields {
+                    encode_fields(idl, &format!("{}.{}", field, variant.name), fields, fields_value, out, depth)?;
+                }
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Raw bytes of a PDA seed value: strings and byte arrays go in as-is, like
+/// `as_ref()` in the program; everything else uses its Borsh encoding.
+fn seed_value_bytes(idl: &Idl, field: &str, ty: &IdlType, value: &Value) -> Result<Vec<u8>, ApiError> {
+    match ty {
+        IdlType::String => value.as_str()
+            .map(|s| s.as_bytes().to_vec())
+            .ok_or_else(|| ApiError::invalid_field(field, "expected a string")),
+        IdlType::Bytes => bytes(field, value),
+        IdlType::Vec(inner) if matches!(**inner, IdlType::U8) => bytes(field, value),
+        _ => {
+            let mut out = Vec::new();
+            encode_idl_value(idl, field, ty, value, &mut out)?;
+            Ok(out)
+        }
+    }
+}
+
+/// Bytes of a `const` seed. Newer IDLs give them as a byte array, older ones
+/// as a typed value.
+pub fn const_seed_bytes(idl: &Idl, field: &str, ty: Option<&IdlType>, value: &Value) -> Result<Vec<u8>, ApiError> {
+    match ty {
+        Some(ty) => seed_value_bytes(idl, field, ty, value),
+        None => bytes(field, value),
+    }
+}
+
+/// Bytes of an `arg` seed: the argument at `path`, descending into struct
+/// fields for dotted paths.
+pub fn arg_seed_bytes(idl: &Idl, args: &[IdlField], values: &Map<String, Value>, path: &str) -> Result<Vec<u8>, ApiError> {
+    let mut segments = path.split('.');
+    let first = segments.next().unwrap_or_default();
+    let arg = args.iter()
+        .find(|arg| names_match(&arg.name, first))
+        .ok_or_else(|| ApiError::instruction_build(format!("PDA seed refers to unknown argument '{}'", path)))?;
+    let mut field = format!("args.{}", arg.name);
+    let mut ty = &arg.ty;
+    let mut value = find_named(values, &arg.name)
+        .ok_or_else(|| ApiError::invalid_field(&field, "missing"))?;
+
+    for segment in segments {
+        let IdlType::Defined(name) = ty else {
+            return Err(ApiError::instruction_build(format!("PDA seed path '{}' does not match the argument types", path)));
+        };
+        let Some(IdlTypeDefTy::Struct { fields: Some(IdlDefinedFields::Named(fields)) }) = idl.types.get(name) else {
+            return Err(ApiError::instruction_build(format!("PDA seed path '{}' does not match the argument types", path)));
+        };
+        let named = fields.iter()
+            .find(|named| names_match(&named.name, segment))
+            .ok_or_else(|| ApiError::instruction_build(format!("PDA seed path '{}' does not match the argument types", path)))?;
+        field = format!("{}.{}", field, named.name);
+        ty = &named.ty;
+        value = value.as_object()
+            .and_then(|object| find_named(object, &named.name))
+            .ok_or_else(|| ApiError::invalid_field(&field, "missing"))?;
+    }
+
+    seed_value_bytes(idl, &field, ty, value)
+}
+
+fn take<'a>(field: &str, data: &mut &'a [u8], length: usize) -> Result<&'a [u8], ApiError> {
+    if data.len() < length {
+        return Err(ApiError::invalid_field(field, "data ends early"));
+    }
+    let (taken, rest) = data.split_at(length);
+    *data = rest;
+    Ok(taken)
+}
+
+fn take_array<const N: usize>(field: &str, data: &mut &[u8]) -> Result<[u8; N], ApiError> {
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(take(field, data, N)?);
+    Ok(bytes)
+}
+
+/// A Borsh length prefix, checked against what's left so a corrupt length
+/// can't make us allocate or loop far past the end of the data.
+fn take_length(field: &str, data: &mut &[u8]) -> Result<usize, ApiError> {
+    let length = u32::from_le_bytes(take_array(field, data)?) as usize;
+    if length > data.len() {
+        return Err(ApiError::invalid_field(field, format!("length {} runs past the end of the data", length)));
+    }
+    Ok(length)
+}
+
+fn decode_fields(idl: &Idl, field: &str, fields: &IdlDefinedFields, data: &mut &[u8], depth: usize, budget: &mut usize) -> Result<Value, ApiError> {
+    match fields {
+        IdlDefinedFields::Named(fields) => {
+            let mut object = Map::new();
+            for named in fields {
+                let value = decode_value(idl, &format!("{}.{}", field, named.name), &named.ty, data, depth + 1, budget)?;
+                object.insert(named.name.clone(), value);
+            }
+            Ok(Value::Object(object))
+        }
+        IdlDefinedFields::Tuple(types) => types
+            .iter()
+            .enumerate()
+            .map(|(index, ty)| decode_value(idl, &format!("{}[{}]", field, index), ty, data, depth + 1, budget))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+    }
+}
+
+/// Borsh-decodes the IDL type `ty` from the front of `data`, in the same
+/// JSON shapes `encode_idl_value` accepts. 64- and 128-bit integers come
+/// back as strings so they keep their precision.
+///
+/// Zero-sized types take no bytes, so `[[Empty; n]; n]` would decode n²
+/// values out of nothing. Besides one value per byte, at most
+/// `MAX_DECODED_VALUES` more are decoded.
+pub fn decode_idl_value(idl: &Idl, field: &str, ty: &IdlType, data: &mut &[u8]) -> Result<Value, ApiError> {
+    let mut budget = MAX_DECODED_VALUES + data.len();
+    decode_value(idl, field, ty, data, 0, &mut budget)
+}
+
+fn decode_value(idl: &Idl, field: &str, ty: &IdlType, data: &mut &[u8], depth: usize, budget: &mut usize) -> Result<Value, ApiError> {
+    check_depth(field, depth)?;
+    *budget = budget.checked_sub(1)
+        .ok_or_else(|| ApiError::invalid_field(field, "holds too many values to decode"))?;
+    let value = match ty {
+        IdlType::Bool => match take_array::<1>(field, data)? {
+            [0] => Value::Bool(false),
+            [1] => Value::Bool(true),
+            [other] => return Err(ApiError::invalid_field(field, format!("{} is not a valid bool", other))),
+        },
+        IdlType::U8 => Value::from(u8::from_le_bytes(take_array(field, data)?)),
+        IdlType::I8 => Value::from(i8::from_le_bytes(take_array(field, data)?)),
+        IdlType::U16 => Value::from(u16::from_le_bytes(take_array(field, data)?)),
+        IdlType::I16 => Value::from(i16::from_le_bytes(take_array(field, data)?)),
+        IdlType::U32 => Value::from(u32::from_le_bytes(take_array(field, data)?)),
+        IdlType::I32 => Value::from(i32::from_le_bytes(take_array(field, data)?)),
+        IdlType::U64 => Value::String(u64::from_le_bytes(take_array(field, data)?).to_string()),
+        IdlType::I64 => Value::String(i64::from_le_bytes(take_array(field, data)?).to_string()),
+        IdlType::U128 => Value::String(u128::from_le_bytes(take_array(field, data)?).to_string()),
+        IdlType::I128 => Value::String(i128::from_le_bytes(take_array(field, data)?).to_string()),
+        IdlType::F32 => Value::from(f32::from_le_bytes(take_array(field, data)?)),
+        IdlType::F64 => Value::from(f64::from_le_bytes(take_array(field, data)?)),
+        IdlType::Bytes => {
+            let length = take_length(field, data)?;
+            Value::String(STANDARD.encode(take(field, data, length)?))
+        }
+        IdlType::String => {
+            let length = take_length(field, data)?;
+            let bytes = take(field, data, length)?;
+            let value = std::str::from_utf8(bytes)
+                .map_err(|_| ApiError::invalid_field(field, "string is not valid UTF-8"))?;
+            Value::String(value.to_string())
+        }
+        IdlType::Pubkey => Value::String(Pubkey::new_from_array(take_array(field, data)?).to_string()),
+        IdlType::Option(inner) => match take_array::<1>(field, data)? {
+            [0] => Value::Null,
+            [1] => decode_value(idl, field, inner, data, depth + 1, budget)?,
+            [other] => return Err(ApiError::invalid_field(field, format!("{} is not a valid option tag", other))),
+        },
+        IdlType::COption(inner) => match u32::from_le_bytes(take_array(field, data)?) {
+            0 => {
+                // A COption is fixed size, so None still has the value's bytes
+                decode_value(idl, field, inner, data, depth + 1, budget)?;
+                Value::Null
+            }
+            1 => decode_value(idl, field, inner, data, depth + 1, budget)?,
+            other => return Err(ApiError::invalid_field(field, format!("{} is not a valid option tag", other))),
+        },
+        IdlType::Vec(inner) => {
+            let length = take_length(field, data)?;
+            (0..length)
+                .map(|index| decode_value(idl, &format!("{}[{}]", field, index), inner, data, depth + 1, budget))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array)?
+        }
+        // The length comes from the IDL, so it gets the same check as a
+        // length prefix before anything is allocated
+        IdlType::Array(_, length) if *length > data.len() => {
+            return Err(ApiError::invalid_field(field, format!("array of {} runs past the end of the data", length)));
+        }
+        IdlType::Array(inner, length) => (0..*length)
+            .map(|index| decode_value(idl, &format!("{}[{}]", field, index), inner, data, depth + 1, budget))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array)?,
+        IdlType::Defined(name) => match idl.type_def(field, name)? {
+            IdlTypeDefTy::Struct { fields: Some(fields) } => decode_fields(idl, field, fields, data, depth, budget)?,
+            IdlTypeDefTy::Struct { fields: None } => Value::Object(Map::new()),
+            IdlTypeDefTy::Type { alias } => decode_value(idl, field, alias, data, depth + 1, budget)?,
+            IdlTypeDefTy::Enum { variants } => {
+                let [index] = take_array::<1>(field, data)?;
+                let variant = variants.get(index as usize)
+                    .ok_or_else(|| ApiError::invalid_field(field, format!("{} is not a variant of {}", index, name)))?;
+                match &variant.fields {
+                    None => Value::String(variant.name.clone()),
+                    Some(IdlDefinedFields::Named(fields)) if fields.is_empty() => Value::String(variant.name.clone()),
+                    Some(IdlDefinedFields::Tuple(types)) if types.is_empty() => Value::String(variant.name.clone()),
+                    Some(fields) => {
+                        let fields = decode_fields(idl, &format!("{}.{}", field, variant.name), fields, data, depth, budget)?;
+                        let mut object = Map::new();
+                        object.insert(variant.name.clone(), fields);
+                        Value::Object(object)
+                    }
+                }
+            }
+        },
+    };
+
+    Ok(value)
+}
+
+impl Idl {
+    /// The account whose discriminator starts `data`.
+    pub fn account_for(&self, data: &[u8]) -> Option<&IdlDiscriminated> {
+        self.accounts.iter().find(|account| data.starts_with(&account.discriminator))
+    }
+
+    /// The event whose discriminator starts `data`.
+    pub fn event_for(&self, data: &[u8]) -> Option<&IdlDiscriminated> {
+        self.events.iter().find(|event| data.starts_with(&event.discriminator))
+    }
+
+    /// Decodes the body of an account or event, after its discriminator,
+    /// using the type of the same name.
+    pub fn decode_discriminated(&self, field: &str, item: &IdlDiscriminated, data: &[u8]) -> Result<Value, ApiError> {
+        let mut body = &data[item.discriminator.len().min(data.len())..];
+        decode_idl_value(self, field, &IdlType::Defined(item.name.clone()), &mut body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn test_idl() -> Idl {
+        parse_idl(json!({
+            "address": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS",
+            "instructions": [],
+            "types": [
+                {
+                    "name": "Params",
+                    "type": { "kind": "struct", "fields": [
+                        { "name": "id", "type": "u64" },
+                        { "name": "label", "type": { "option": "string" } },
+                        { "name": "mode", "type": { "defined": { "name": "Mode" } } },
+                        { "name": "owners", "type": { "vec": "pubkey" } },
+                        { "name": "ratios", "type": { "array": ["u16", 3] } },
+                        { "name": "blob", "type": "bytes" },
+                        { "name": "point", "type": { "defined": { "name": "Point" } } }
+                    ] }
+                },
+                {
+                    "name": "Mode",
+                    "type": { "kind": "enum", "variants": [
+                        { "name": "Fast" },
+                        { "name": "Slow", "fields": [{ "name": "delay", "type": "u32" }] },
+                        { "name": "Pair", "fields": ["i8", "bool"] }
+                    ] }
+                },
+                { "name": "Point", "type": { "kind": "struct", "fields": ["i64", "i64"] } },
+                { "name": "Amount", "type": { "kind": "type", "alias": "u128" } }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn encode(idl: &Idl, ty: &IdlType, value: &Value) -> Vec<u8> {
+        let mut out = Vec::new();
+        encode_idl_value(idl, "value", ty, value, &mut out).unwrap();
+        out
+    }
+
+    fn round_trip(idl: &Idl, ty: IdlType, value: Value) {
+        let bytes = encode(idl, &ty, &value);
+        let mut data = bytes.as_slice();
+        let decoded = decode_idl_value(idl, "value", &ty, &mut data).unwrap();
+        assert_eq!(decoded, value);
+        assert!(data.is_empty(), "{} bytes left over", data.len());
+    }
+
+    fn defined(name: &str) -> IdlType {
+        IdlType::Defined(name.to_string())
+    }
+
+    #[test]
+    fn sighash_matches_anchor_discriminators() {
+        assert_eq!(sighash("global", "initialize"), [175, 175, 109, 31, 13, 152, 155, 237]);
+        assert_eq!(sighash("global", "do_thing"), [72, 36, 181, 152, 19, 6, 103, 41]);
+        assert_eq!(sighash("account", "Counter"), [255, 176, 4, 245, 188, 253, 124, 25]);
+        assert_eq!(sighash("event", "Initialized"), [208, 213, 115, 98, 115, 82, 201, 209]);
+    }
+
+    #[test]
+    fn converts_instruction_names_to_snake_case() {
+        assert_eq!(to_snake_case("initialize"), "initialize");
+        assert_eq!(to_snake_case("doThing"), "do_thing");
+        assert_eq!(to_snake_case("initV2"), "init_v2");
+        assert_eq!(to_snake_case("v2Init"), "v2_init");
+        assert_eq!(to_snake_case("createATA"), "create_ata");
+        assert_eq!(to_snake_case("parseHTTPRequest"), "parse_http_request");
+    }
+
+    #[test]
+    fn matches_names_across_casing() {
+        assert!(names_match("vaultBump", "vault_bump"));
+        assert!(names_match("VaultBump", "vaultbump"));
+        assert!(!names_match("vault", "vaults"));
+
+        let map = json!({ "system_program": 1, "payer": 2 });
+        let map = map.as_object().unwrap();
+        assert_eq!(find_named(map, "systemProgram"), Some(&json!(1)));
+        assert_eq!(find_named(map, "payer"), Some(&json!(2)));
+        assert_eq!(find_named(map, "authority"), None);
+    }
+
+    #[test]
+    fn parses_legacy_idls() {
+        let idl = parse_idl(json!({
+            "version": "0.1.0",
+            "name": "legacy",
+            "metadata": { "address": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS" },
+            "instructions": [{
+                "name": "doThing",
+                "accounts": [
+                    { "name": "user", "isMut": true, "isSigner": true },
+                    { "name": "extra", "isMut": false, "isSigner": false, "isOptional": true }
+                ],
+                "args": [{ "name": "owner", "type": "publicKey" }]
+            }],
+            "accounts": [{ "name": "Counter", "type": { "kind": "struct", "fields": [{ "name": "count", "type": "u64" }] } }],
+            "events": [{ "name": "Initialized", "fields": [{ "name": "id", "type": "u64", "index": false }] }]
+        }))
+        .unwrap();
+
+        assert_eq!(idl.program_id.unwrap().to_string(), "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+        let instruction = idl.instruction("do_thing").unwrap();
+        assert_eq!(instruction.discriminator, sighash("global", "do_thing"));
+        assert!(matches!(instruction.args[0].ty, IdlType::Pubkey));
+        let IdlAccountItem::Single(user) = &instruction.accounts[0] else { panic!("expected a single account") };
+        assert!(user.writable && user.signer && !user.optional);
+        let IdlAccountItem::Single(extra) = &instruction.accounts[1] else { panic!("expected a single account") };
+        assert!(extra.optional);
+
+        // Inline account and event layouts end up in the type table
+        assert_eq!(idl.accounts[0].discriminator, sighash("account", "Counter"));
+        assert_eq!(idl.events[0].discriminator, sighash("event", "Initialized"));
+        assert!(idl.types.contains_key("Counter"));
+        assert!(idl.types.contains_key("Initialized"));
+    }
+
+    #[test]
+    fn parses_current_idls() {
+        let idl = parse_idl(json!({
+            "address": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS",
+            "metadata": { "name": "demo", "version": "0.1.0", "spec": "0.1.0" },
+            "instructions": [{
+                "name": "initialize",
+                "discriminator": [1, 2, 3, 4, 5, 6, 7, 8],
+                "accounts": [
+                    {
+                        "name": "counter",
+                        "writable": true,
+                        "pda": { "seeds": [{ "kind": "const", "value": [99] }, { "kind": "account", "path": "authority" }] }
+                    },
+                    { "name": "authority", "writable": true, "signer": true },
+                    { "name": "system_program", "address": "11111111111111111111111111111111" }
+                ],
+                "args": []
+            }],
+            "accounts": [{ "name": "Counter", "discriminator": [9, 9, 9, 9, 9, 9, 9, 9] }],
+            "types": [{ "name": "Counter", "type": { "kind": "struct", "fields": [{ "name": "count", "type": "u64" }] } }]
+        }))
+        .unwrap();
+
+        let instruction = idl.instruction("initialize").unwrap();
+        assert_eq!(instruction.discriminator, [1, 2, 3, 4, 5, 6, 7, 8]);
+        let IdlAccountItem::Single(counter) = &instruction.accounts[0] else { panic!("expected a single account") };
+        let pda = counter.pda.as_ref().unwrap();
+        assert!(matches!(&pda.seeds[1], IdlSeed::Account { path } if path == "authority"));
+        let IdlAccountItem::Single(system_program) = &instruction.accounts[2] else { panic!("expected a single account") };
+        assert_eq!(system_program.address.as_deref(), Some("11111111111111111111111111111111"));
+
+        assert_eq!(idl.accounts[0].discriminator, [9; 8]);
+        assert_eq!(idl.account_for(&[9, 9, 9, 9, 9, 9, 9, 9, 0]).map(|account| account.name.as_str()), Some("Counter"));
+        assert!(idl.account_for(&[9, 9, 9]).is_none());
+    }
+
+    #[test]
+    fn rejects_unsupported_idl_types() {
+        let generic = json!({
+            "instructions": [],
+            "types": [{ "name": "A", "type": { "kind": "struct", "fields": [{ "name": "x", "type": { "generic": "T" } }] } }]
+        });
+        assert!(parse_idl(generic).is_err());
+
+        let alias_loop = json!({
+            "instructions": [],
+            "types": [
+                { "name": "A", "type": { "kind": "type", "alias": { "defined": { "name": "B" } } } },
+                { "name": "B", "type": { "kind": "type", "alias": { "array": [{ "defined": { "name": "A" } }, 1] } } }
+            ]
+        });
+        assert!(parse_idl(alias_loop).is_err());
+
+        // Recursion behind an option is fine: the data decides where it ends
+        let list = json!({
+            "instructions": [],
+            "types": [{ "name": "Node", "type": { "kind": "struct", "fields": [{ "name": "next", "type": { "option": { "defined": { "name": "Node" } } } }] } }]
+        });
+        assert!(parse_idl(list).is_ok());
+    }
+
+    #[test]
+    fn encodes_known_borsh_layouts() {
+        let idl = test_idl();
+        assert_eq!(encode(&idl, &IdlType::U64, &json!("18446744073709551615")), [0xff; 8]);
+        assert_eq!(encode(&idl, &IdlType::I16, &json!(-2)), [0xfe, 0xff]);
+        assert_eq!(encode(&idl, &IdlType::String, &json!("ab")), [2, 0, 0, 0, b'a', b'b']);
+        assert_eq!(encode(&idl, &IdlType::Option(Box::new(IdlType::U8)), &Value::Null), [0]);
+        assert_eq!(encode(&idl, &IdlType::Option(Box::new(IdlType::U8)), &json!(7)), [1, 7]);
+        assert_eq!(encode(&idl, &IdlType::COption(Box::new(IdlType::U8)), &json!(7)), [1, 0, 0, 0, 7]);
+        assert_eq!(encode(&idl, &IdlType::Bytes, &json!("AQI=")), [2, 0, 0, 0, 1, 2]);
+        assert_eq!(encode(&idl, &defined("Mode"), &json!("Fast")), [0]);
+        assert_eq!(encode(&idl, &defined("Mode"), &json!({ "slow": { "delay": 5 } })), [1, 5, 0, 0, 0]);
+        assert_eq!(encode(&idl, &defined("Mode"), &json!({ "Pair": [-1, true] })), [2, 0xff, 1]);
+        assert_eq!(encode(&idl, &defined("Amount"), &json!("1")), [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn rejects_values_that_do_not_fit() {
+        let idl = test_idl();
+        let mut out = Vec::new();
+        assert!(encode_idl_value(&idl, "value", &IdlType::U8, &json!(256), &mut out).is_err());
+        assert!(encode_idl_value(&idl, "value", &IdlType::U64, &json!(-1), &mut out).is_err());
+        assert!(encode_idl_value(&idl, "value", &IdlType::Array(Box::new(IdlType::U8), 3), &json!([1, 2]), &mut out).is_err());
+        assert!(encode_idl_value(&idl, "value", &defined("Mode"), &json!("Medium"), &mut out).is_err());
+
+        let error = encode_idl_value(&idl, "params", &defined("Params"), &json!({ "id": 1 }), &mut out).unwrap_err();
+        assert_eq!(error.field(), Some("params.label"));
+    }
+
+    #[test]
+    fn round_trips_borsh_values() {
+        let idl = test_idl();
+        round_trip(&idl, defined("Mode"), json!("Fast"));
+        round_trip(&idl, defined("Mode"), json!({ "Slow": { "delay": 9 } }));
+        round_trip(&idl, defined("Mode"), json!({ "Pair": [-3, false] }));
+        round_trip(&idl, IdlType::Option(Box::new(IdlType::String)), Value::Null);
+        round_trip(&idl, IdlType::Option(Box::new(IdlType::String)), json!("set"));
+        round_trip(&idl, IdlType::Vec(Box::new(IdlType::Vec(Box::new(IdlType::U8)))), json!([[1, 2], [], [3]]));
+        round_trip(&idl, IdlType::Array(Box::new(IdlType::Bool), 2), json!([true, false]));
+        round_trip(&idl, IdlType::I128, json!("-170141183460469231731687303715884105728"));
+        round_trip(&idl, IdlType::U128, json!("340282366920938463463374607431768211455"));
+        round_trip(&idl, defined("Params"), json!({
+            "id": "7",
+            "label": null,
+            "mode": { "Slow": { "delay": 5 } },
+            "owners": ["11111111111111111111111111111111", "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"],
+            "ratios": [1, 2, 65535],
+            "blob": "3q2+7w==",
+            "point": ["-5", "5"]
+        }));
+    }
+
+    #[test]
+    fn rejects_malformed_borsh_data() {
+        let idl = test_idl();
+        let decode = |ty: &IdlType, bytes: &[u8]| {
+            let mut data = bytes;
+            decode_idl_value(&idl, "data", ty, &mut data)
+        };
+
+        assert!(decode(&IdlType::U32, &[1, 2]).is_err());
+        assert!(decode(&IdlType::Bool, &[2]).is_err());
+        assert!(decode(&IdlType::Option(Box::new(IdlType::U8)), &[3, 0]).is_err());
+        assert!(decode(&defined("Mode"), &[7]).is_err());
+        assert!(decode(&IdlType::String, &[2, 0, 0, 0, 0xff, 0xfe]).is_err());
+        // A length prefix longer than the data fails before allocating
+        assert!(decode(&IdlType::Vec(Box::new(IdlType::U8)), &[0xff, 0xff, 0xff, 0xff]).is_err());
+        assert!(decode(&IdlType::Array(Box::new(defined("Empty")), usize::MAX), &[]).is_err());
+    }
+
+    #[test]
+    fn derives_seed_bytes_like_the_program() {
+        let idl = test_idl();
+        let args = vec![
+            IdlField { name: "name".to_string(), ty: IdlType::String },
+            IdlField { name: "params".to_string(), ty: defined("Params") },
+        ];
+        let values = json!({ "name": "vault", "params": { "id": 258 } });
+        let values = values.as_object().unwrap();
+
+        assert_eq!(arg_seed_bytes(&idl, &args, values, "name").unwrap(), b"vault");
+        assert_eq!(arg_seed_bytes(&idl, &args, values, "params.id").unwrap(), 258u64.to_le_bytes());
+        assert!(arg_seed_bytes(&idl, &args, values, "missing").is_err());
+        assert_eq!(const_seed_bytes(&idl, "seed", None, &json!([1, 2])).unwrap(), [1, 2]);
+        assert_eq!(const_seed_bytes(&idl, "seed", Some(&IdlType::String), &json!("ab")).unwrap(), b"ab");
+    }
+}
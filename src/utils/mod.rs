@@ -1,5 +1,13 @@
+pub mod borsh;
 pub mod crypto;
+pub mod decode;
+pub mod extract;
+pub mod idl;
 pub mod solana;
 
+pub use borsh::*;
 pub use crypto::*;
+pub use decode::*;
+pub use extract::*;
+pub use idl::*;
 pub use solana::*;
@@ -1,13 +1,38 @@
-use crate::models::{AccountMeta, InstructionResponse, TransactionResponse};
+use crate::models::{
+    AccountMeta, AddressLookupTableSpec, ApiError, ComputeBudgetOptions, InstructionResponse,
+    SignedTransactionResponse, TransactionEncoding, TransactionOptions, TransactionResponse,
+    TransactionSignature, TransactionVersion,
+};
 use anyhow::{anyhow, Result};
-use solana_program::instruction::Instruction;
+use solana_program::{
+    address_lookup_table::AddressLookupTableAccount,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, Message, VersionedMessage},
+    system_instruction,
+};
 use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    packet::PACKET_DATA_SIZE,
     pubkey::Pubkey,
-    transaction::Transaction,
+    signature::Signature,
+    transaction::{Transaction, VersionedTransaction},
 };
 use std::str::FromStr;
 use base64::{engine::general_purpose::STANDARD, Engine};
 
+// Mirrors the runtime's compute budget defaults (solana-program-runtime)
+const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+/// Instructions produced by one of the endpoint builders, together with the
+/// account that pays fees when the caller doesn't choose one.
+pub struct InstructionBatch {
+    pub instructions: Vec<Instruction>,
+    pub default_fee_payer: Pubkey,
+}
+
 pub fn validate_pubkey(pubkey_str: &str) -> Result<Pubkey> {
     Pubkey::from_str(pubkey_str).map_err(|e| anyhow!("Invalid pubkey '{}': {}", pubkey_str, e))
 }
@@ -30,22 +55,261 @@ pub fn instruction_to_response(instruction: Instruction) -> InstructionResponse
     }
 }
 
-pub fn instruction_to_transaction(instruction: Instruction, fee_payer: Option<Pubkey>) -> Result<TransactionResponse> {
-    // Create a transaction with a default blockhash and fee payer
-    // The client should replace these with actual values before signing
-    let default_fee_payer = fee_payer.unwrap_or_else(|| Pubkey::default());
+pub fn instruction_to_transaction(
+    instructions: &[Instruction],
+    default_fee_payer: Pubkey,
+    options: &TransactionOptions,
+) -> Result<TransactionResponse, ApiError> {
+    let fee_payer = match &options.fee_payer {
+        Some(fee_payer) => validate_pubkey(fee_payer)
+            .map_err(|e| ApiError::invalid_pubkey("feePayer", e))?,
+        None => default_fee_payer,
+    };
+
+    // Without a caller-supplied blockhash the message carries the zero hash
+    // and the client must replace it before signing
+    let mut recent_blockhash = match &options.recent_blockhash {
+        Some(blockhash) => Hash::from_str(blockhash)
+            .map_err(|e| ApiError::invalid_field("recentBlockhash", e))?,
+        None => Hash::default(),
+    };
+
+    // A durable nonce transaction must advance the nonce in its first
+    // instruction and carries the stored nonce in place of the blockhash
+    let mut all_instructions = Vec::new();
+    if let Some(nonce) = &options.nonce {
+        if options.recent_blockhash.is_some() {
+            return Err(ApiError::invalid_field(
+                "recentBlockhash",
+                "cannot be combined with a durable nonce; the nonce value is used as the blockhash",
+            ));
+        }
+
+        let nonce_account = validate_pubkey(&nonce.account)
+            .map_err(|e| ApiError::invalid_pubkey("nonce.account", e))?;
+        let nonce_authority = validate_pubkey(&nonce.authority)
+            .map_err(|e| ApiError::invalid_pubkey("nonce.authority", e))?;
+        recent_blockhash = Hash::from_str(&nonce.value)
+            .map_err(|e| ApiError::invalid_field("nonce.value", e))?;
+
+        all_instructions.push(system_instruction::advance_nonce_account(&nonce_account, &nonce_authority));
+    }
+
+    // ComputeBudget instructions go in front of the caller's instructions.
+    // The nonce advance counts toward the default unit limit like any other
+    let max_priority_fee_lamports = match &options.compute_budget {
+        Some(budget) => {
+            let instruction_count = all_instructions.len() + instructions.len();
+            let (budget_instructions, max_priority_fee) = compute_budget_instructions(budget, instruction_count)?;
+            all_instructions.extend(budget_instructions);
+            max_priority_fee
+        }
+        None => None,
+    };
+    all_instructions.extend_from_slice(instructions);
+
+    let serialized = match options.version.unwrap_or_default() {
+        TransactionVersion::Legacy => {
+            if !options.address_lookup_tables.is_empty() {
+                return Err(ApiError::invalid_field(
+                    "addressLookupTables",
+                    "address lookup tables require \"version\": \"v0\"",
+                ));
+            }
+
+            let message = Message::new_with_blockhash(&all_instructions, Some(&fee_payer), &recent_blockhash);
+            let transaction = Transaction::new_unsigned(message);
+
+            // Serialize the transaction using bincode, which is what Solana uses internally
+            bincode::serialize(&transaction).map_err(ApiError::transaction_build)?
+        }
+        TransactionVersion::V0 => {
+            let lookup_tables = parse_lookup_tables(&options.address_lookup_tables)?;
+            let message = v0::Message::try_compile(&fee_payer, &all_instructions, &lookup_tables, recent_blockhash)
+                .map_err(ApiError::transaction_build)?;
 
-    let transaction = Transaction::new_with_payer(&[instruction], Some(&default_fee_payer));
+            let transaction = VersionedTransaction {
+                signatures: vec![Signature::default(); message.header.num_required_signatures as usize],
+                message: VersionedMessage::V0(message),
+            };
 
-    // Serialize the transaction using bincode, which is what Solana uses internally
-    let serialized = bincode::serialize(&transaction).map_err(|e| {
-        anyhow!("Failed to serialize transaction: {}", e)
-    })?;
+            bincode::serialize(&transaction).map_err(ApiError::transaction_build)?
+        }
+    };
+
+    // Anything bigger can never be sent, so fail here rather than at submission
+    if serialized.len() > PACKET_DATA_SIZE {
+        return Err(ApiError::TransactionTooLarge { size: serialized.len(), limit: PACKET_DATA_SIZE });
+    }
 
     let base64_transaction = STANDARD.encode(&serialized);
 
     Ok(TransactionResponse {
         transaction: base64_transaction,
         message: Some("Transaction created successfully".to_string()),
+        max_priority_fee_lamports,
+    })
+}
+
+fn parse_lookup_tables(specs: &[AddressLookupTableSpec]) -> Result<Vec<AddressLookupTableAccount>, ApiError> {
+    specs
+        .iter()
+        .enumerate()
+        .map(|(index, spec)| {
+            let field = format!("addressLookupTables[{}]", index);
+            let key = validate_pubkey(&spec.address)
+                .map_err(|e| ApiError::invalid_pubkey(format!("{}.address", field), e))?;
+
+            let addresses = spec
+                .addresses
+                .iter()
+                .enumerate()
+                .map(|(i, address)| {
+                    validate_pubkey(address)
+                        .map_err(|e| ApiError::invalid_pubkey(format!("{}.addresses[{}]", field, i), e))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(AddressLookupTableAccount { key, addresses })
+        })
+        .collect()
+}
+
+/// Returns the ComputeBudget instructions to prepend and the resulting maximum
+/// priority fee (unit limit x unit price, rounded up to whole lamports).
+fn compute_budget_instructions(
+    budget: &ComputeBudgetOptions,
+    instruction_count: usize,
+) -> Result<(Vec<Instruction>, Option<u64>), ApiError> {
+    let mut budget_instructions = Vec::with_capacity(2);
+
+    if let Some(unit_limit) = budget.unit_limit {
+        if unit_limit > MAX_COMPUTE_UNIT_LIMIT {
+            return Err(ApiError::invalid_field(
+                "computeBudget.unitLimit",
+                format!("must not exceed {}", MAX_COMPUTE_UNIT_LIMIT),
+            ));
+        }
+        budget_instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(unit_limit));
+    }
+
+    let max_priority_fee = budget.micro_lamports_per_unit.map(|price| {
+        budget_instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+
+        // Without an explicit limit the runtime grants the default per instruction
+        let unit_limit = budget.unit_limit.unwrap_or_else(|| {
+            (DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT as usize)
+                .saturating_mul(instruction_count)
+                .min(MAX_COMPUTE_UNIT_LIMIT as usize) as u32
+        });
+        let micro_lamports = unit_limit as u128 * price as u128;
+        micro_lamports.div_ceil(MICRO_LAMPORTS_PER_LAMPORT) as u64
+    });
+
+    Ok((budget_instructions, max_priority_fee))
+}
+
+/// Parses a serialized legacy or versioned transaction. Legacy transactions
+/// deserialize as `VersionedTransaction` too, so callers handle both the same way.
+pub fn deserialize_transaction(
+    field: &str,
+    encoded: &str,
+    encoding: Option<TransactionEncoding>,
+) -> Result<VersionedTransaction, ApiError> {
+    let bytes = match encoding {
+        Some(TransactionEncoding::Base64) => STANDARD.decode(encoded)
+            .map_err(|e| ApiError::invalid_field(field, format!("failed to decode base64: {}", e)))?,
+        Some(TransactionEncoding::Base58) => bs58::decode(encoded).into_vec()
+            .map_err(|e| ApiError::invalid_field(field, format!("failed to decode base58: {}", e)))?,
+        // Base58 output only uses characters that are also valid base64, but
+        // it is almost never valid base64 of the right shape, so try that first
+        None => STANDARD.decode(encoded)
+            .or_else(|_| bs58::decode(encoded).into_vec())
+            .map_err(|_| ApiError::invalid_field(field, "transaction is neither valid base64 nor base58"))?,
+    };
+
+    let transaction: VersionedTransaction = bincode::deserialize(&bytes)
+        .map_err(|e| ApiError::invalid_field(field, format!("not a serialized transaction: {}", e)))?;
+
+    transaction.sanitize()
+        .map_err(|e| ApiError::invalid_field(field, format!("malformed transaction: {}", e)))?;
+
+    Ok(transaction)
+}
+
+/// Pairs every signature slot with the signer it belongs to.
+pub fn signature_report(transaction: &VersionedTransaction) -> Vec<TransactionSignature> {
+    transaction
+        .signatures
+        .iter()
+        .zip(transaction.message.static_account_keys())
+        .map(|(signature, key)| TransactionSignature {
+            pubkey: key.to_string(),
+            signature: STANDARD.encode(signature),
+            present: *signature != Signature::default(),
+        })
+        .collect()
+}
+
+/// Builds the response for endpoints that hand a (partially) signed
+/// transaction back to the caller.
+pub fn signed_transaction_response(transaction: &VersionedTransaction) -> Result<SignedTransactionResponse, ApiError> {
+    let serialized = bincode::serialize(transaction).map_err(ApiError::transaction_build)?;
+    let signatures = signature_report(transaction);
+    let missing_signers: Vec<String> = signatures
+        .iter()
+        .filter(|status| !status.present)
+        .map(|status| status.pubkey.clone())
+        .collect();
+
+    Ok(SignedTransactionResponse {
+        transaction: STANDARD.encode(serialized),
+        complete: missing_signers.is_empty(),
+        signatures,
+        missing_signers,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NonceOptions;
+
+    fn priced_options(nonce: bool) -> TransactionOptions {
+        TransactionOptions {
+            compute_budget: Some(ComputeBudgetOptions { unit_limit: None, micro_lamports_per_unit: Some(1_000_000) }),
+            nonce: nonce.then(|| NonceOptions {
+                account: Pubkey::new_unique().to_string(),
+                authority: Pubkey::new_unique().to_string(),
+                value: Hash::new_unique().to_string(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn default_unit_limit_counts_the_nonce_advance() {
+        let payer = Pubkey::new_unique();
+        let transfer = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+
+        let plain = instruction_to_transaction(std::slice::from_ref(&transfer), payer, &priced_options(false)).unwrap();
+        assert_eq!(plain.max_priority_fee_lamports, Some(200_000));
+
+        let with_nonce = instruction_to_transaction(&[transfer], payer, &priced_options(true)).unwrap();
+        assert_eq!(with_nonce.max_priority_fee_lamports, Some(400_000));
+    }
+
+    #[test]
+    fn rejects_transactions_over_the_packet_limit() {
+        let payer = Pubkey::new_unique();
+        let instruction = |size: usize| Instruction::new_with_bytes(Pubkey::new_unique(), &vec![0; size], Vec::new());
+
+        assert!(instruction_to_transaction(&[instruction(900)], payer, &TransactionOptions::default()).is_ok());
+
+        let error = instruction_to_transaction(&[instruction(1300)], payer, &TransactionOptions::default())
+            .err()
+            .expect("oversized transaction accepted");
+        assert_eq!(error.code(), "TRANSACTION_TOO_LARGE");
+        assert_eq!(error.status(), axum::http::StatusCode::BAD_REQUEST);
+    }
+}
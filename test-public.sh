@@ -73,7 +73,7 @@ echo ""
 echo "7. Testing POST /send/token"
 TOKEN_TRANSFER_RESPONSE=$(curl -s -X POST "$PUBLIC_URL/send/token" \
   -H "Content-Type: application/json" \
-  -d "{\"destination\": \"33333333333333333333333333333333\", \"mint\": \"22222222222222222222222222222222\", \"owner\": \"$PUBKEY\", \"amount\": 1000000}")
+  -d "{\"destination\": \"33333333333333333333333333333333\", \"mint\": \"22222222222222222222222222222222\", \"owner\": \"$PUBKEY\", \"amount\": 1000000, \"decimals\": 6}")
 echo "Response format: $(echo $TOKEN_TRANSFER_RESPONSE | jq 'keys')"
 echo "✅ Token Transfer Transaction: $(echo $TOKEN_TRANSFER_RESPONSE | jq -r '.transaction' | head -c 50)..."
 echo ""